@@ -1,3 +1,4 @@
+use futures_util::StreamExt;
 use std::error::Error;
 use tracing::{error, info};
 use tradier::utils::logger::setup_logger;
@@ -16,7 +17,7 @@ use tradier::Config;
 /// For each loop iteration:
 ///
 /// 1. **MarketSession**: Attempts to create a market session for streaming real-time quotes and trades.
-///    If successful, it streams data for specified symbols using `MarketSessionPayload`.
+///    If successful, it streams typed events for specified symbols using `MarketSessionPayload`.
 ///    On any streaming error, it logs the issue and attempts to reconnect.
 /// 2. **AccountSession**: Creates an account session to manage account-level WebSocket interactions,
 ///    but does not start streaming in this example. The account session creation is logged.
@@ -57,8 +58,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .linebreak(true)
                     .valid_only(true)
                     .build();
-                if let Err(e) = market_session.ws_stream(payload).await {
-                    error!("Streaming error: {}. Reconnecting...", e);
+                match market_session.ws_stream(payload).await {
+                    Ok(mut events) => {
+                        while let Some(event) = events.next().await {
+                            match event {
+                                Ok(event) => info!("Received: {:?}", event),
+                                Err(e) => error!("Unable to handle event: {}", e),
+                            }
+                        }
+                        info!("Stream ended. Reconnecting...");
+                    }
+                    Err(e) => error!("Streaming error: {}. Reconnecting...", e),
                 }
             }
             Err(e) => {
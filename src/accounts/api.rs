@@ -1,7 +1,14 @@
 use crate::accounts::types::AccountNumber;
 use crate::accounts::types::GetAccountBalancesResponse;
+use crate::accounts::types::{
+    AccountHistoryQuery, ClosedPosition, GainLossQuery, GetAccountGainLossResponse,
+    GetAccountHistoryResponse, GetAccountOrderResponse, GetAccountOrdersResponse, HistoryEvent,
+};
 use crate::types::GetAccountPositionsResponse;
-use crate::{error::Result, utils::Sealed};
+use crate::{
+    error::Result,
+    utils::{PageIter, PageStream, Sealed},
+};
 
 pub mod non_blocking {
     use super::*;
@@ -17,6 +24,55 @@ pub mod non_blocking {
             &self,
             account_number: &AccountNumber,
         ) -> Result<GetAccountPositionsResponse>;
+
+        /// Lists the orders of an account. Order tags are only returned when `include_tags`
+        /// is set.
+        async fn get_account_orders(
+            &self,
+            account_number: &AccountNumber,
+            include_tags: bool,
+        ) -> Result<GetAccountOrdersResponse>;
+
+        /// Fetches a single order, including its legs.
+        async fn get_account_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+            include_tags: bool,
+        ) -> Result<GetAccountOrderResponse>;
+
+        /// Fetches one page of the account's history, filtered by `query`.
+        async fn get_account_history(
+            &self,
+            account_number: &AccountNumber,
+            query: &AccountHistoryQuery,
+        ) -> Result<GetAccountHistoryResponse>;
+
+        /// Streams every history event matching `query`, requesting pages from the query's
+        /// page (or the first one) until an empty page comes back. Up to `prefetch` pages are
+        /// requested concurrently.
+        fn stream_account_history(
+            &self,
+            account_number: &AccountNumber,
+            query: AccountHistoryQuery,
+            prefetch: usize,
+        ) -> PageStream<'_, HistoryEvent>;
+
+        /// Fetches one page of the account's closed positions, filtered and sorted by `query`.
+        async fn get_account_gainloss(
+            &self,
+            account_number: &AccountNumber,
+            query: &GainLossQuery,
+        ) -> Result<GetAccountGainLossResponse>;
+
+        /// Streams every closed position matching `query`, paginating like
+        /// [`stream_account_history`](Accounts::stream_account_history).
+        fn stream_account_gainloss(
+            &self,
+            account_number: &AccountNumber,
+            query: GainLossQuery,
+            prefetch: usize,
+        ) -> PageStream<'_, ClosedPosition>;
     }
 }
 pub mod blocking {
@@ -31,5 +87,54 @@ pub mod blocking {
             &self,
             account_number: &AccountNumber,
         ) -> Result<GetAccountPositionsResponse>;
+
+        /// Lists the orders of an account. Order tags are only returned when `include_tags`
+        /// is set.
+        fn get_account_orders(
+            &self,
+            account_number: &AccountNumber,
+            include_tags: bool,
+        ) -> Result<GetAccountOrdersResponse>;
+
+        /// Fetches a single order, including its legs.
+        fn get_account_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+            include_tags: bool,
+        ) -> Result<GetAccountOrderResponse>;
+
+        /// Fetches one page of the account's history, filtered by `query`.
+        fn get_account_history(
+            &self,
+            account_number: &AccountNumber,
+            query: &AccountHistoryQuery,
+        ) -> Result<GetAccountHistoryResponse>;
+
+        /// Iterates over every history event matching `query`, requesting pages from the
+        /// query's page (or the first one) until an empty page comes back. Up to `prefetch`
+        /// pages are requested concurrently.
+        fn iter_account_history(
+            &self,
+            account_number: &AccountNumber,
+            query: AccountHistoryQuery,
+            prefetch: usize,
+        ) -> PageIter<'_, HistoryEvent>;
+
+        /// Fetches one page of the account's closed positions, filtered and sorted by `query`.
+        fn get_account_gainloss(
+            &self,
+            account_number: &AccountNumber,
+            query: &GainLossQuery,
+        ) -> Result<GetAccountGainLossResponse>;
+
+        /// Iterates over every closed position matching `query`, paginating like
+        /// [`iter_account_history`](Accounts::iter_account_history).
+        fn iter_account_gainloss(
+            &self,
+            account_number: &AccountNumber,
+            query: GainLossQuery,
+            prefetch: usize,
+        ) -> PageIter<'_, ClosedPosition>;
     }
 }
@@ -1,6 +1,7 @@
+use proptest::prelude::*;
 use serde::Serialize;
 
-use crate::{common::test_support::AccountTypeWire, utils::tests::DateTimeUtcWire};
+use crate::utils::tests::DateTimeUtcWire;
 
 #[derive(Debug, Serialize, proptest_derive::Arbitrary)]
 pub struct GetAccountBalancesResponseWire {
@@ -12,7 +13,6 @@ pub struct GetAccountBalancesWire {
     option_short_value: f64,
     total_equity: f64,
     account_number: String,
-    account_type: AccountTypeWire,
     close_pl: f64,
     current_requirement: f64,
     equity: f64,
@@ -27,7 +27,24 @@ pub struct GetAccountBalancesWire {
     total_cash: f64,
     uncleared_funds: f64,
     pending_cash: f64,
-    margin: MarginWire,
+    #[serde(flatten)]
+    details: BalanceDetailsWire,
+}
+
+/// The account-type-specific block of a balances response, tagged with its `account_type`.
+#[derive(Debug, Serialize, proptest_derive::Arbitrary)]
+#[serde(tag = "account_type", rename_all = "lowercase")]
+pub enum BalanceDetailsWire {
+    Cash { cash: CashWire },
+    Margin { margin: MarginWire },
+    Pdt { pdt: PdtWire },
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct CashWire {
+    cash_available: f64,
+    sweep: f64,
+    unsettled_funds: f64,
 }
 
 #[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
@@ -40,6 +57,17 @@ pub struct MarginWire {
     sweep: f64,
 }
 
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct PdtWire {
+    fed_call: f64,
+    maintenance_call: f64,
+    option_buying_power: f64,
+    stock_buying_power: f64,
+    stock_short_value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day_trade_buying_power: Option<f64>,
+}
+
 #[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
 pub struct PositionWire {
     cost_basis: f64,
@@ -50,5 +78,156 @@ pub struct PositionWire {
 }
 #[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
 pub struct GetAccountPositionsResponseWire {
-    positions: Vec<PositionWire>,
+    positions: PositionsWire,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct PositionsWire {
+    position: Vec<PositionWire>,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct GetAccountOrdersResponseWire {
+    orders: AccountOrdersWire,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct AccountOrdersWire {
+    #[proptest(strategy = "prop::collection::vec(any::<OrderWire>(), 1..8)")]
+    order: Vec<OrderWire>,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct GetAccountOrderResponseWire {
+    order: OrderWire,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OrderWire {
+    id: u64,
+    #[serde(rename = "type")]
+    #[proptest(
+        strategy = "one_of(&[\"market\", \"limit\", \"stop\", \"stop_limit\", \"debit\", \"credit\", \"even\"])"
+    )]
+    order_type: String,
+    symbol: String,
+    #[proptest(
+        strategy = "one_of(&[\"buy\", \"sell_short\", \"buy_to_open\", \"sell_to_close\"])"
+    )]
+    side: String,
+    quantity: f64,
+    status: OrderStatusWire,
+    #[proptest(strategy = "one_of(&[\"day\", \"gtc\", \"pre\", \"post\"])")]
+    duration: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<f64>,
+    avg_fill_price: f64,
+    exec_quantity: f64,
+    last_fill_price: f64,
+    last_fill_quantity: f64,
+    remaining_quantity: f64,
+    create_date: DateTimeUtcWire,
+    transaction_date: DateTimeUtcWire,
+    #[proptest(
+        strategy = "one_of(&[\"equity\", \"option\", \"multileg\", \"combo\", \"oto\", \"oco\", \"otoco\"])"
+    )]
+    class: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatusWire {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Expired,
+    Canceled,
+    Pending,
+    Rejected,
+    Error,
+}
+
+fn one_of(values: &'static [&'static str]) -> impl Strategy<Value = String> {
+    prop::sample::select(values).prop_map(str::to_owned)
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct GetAccountGainLossResponseWire {
+    gainloss: GainLossWire,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct GainLossWire {
+    closed_position: Vec<ClosedPositionWire>,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct ClosedPositionWire {
+    close_date: DateTimeUtcWire,
+    cost: f64,
+    gain_loss: f64,
+    gain_loss_percent: f64,
+    open_date: DateTimeUtcWire,
+    proceeds: f64,
+    quantity: f64,
+    symbol: String,
+    term: u32,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct GetAccountHistoryResponseWire {
+    history: HistoryWire,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct HistoryWire {
+    #[proptest(strategy = "prop::collection::vec(any::<HistoryEventWire>(), 1..8)")]
+    event: Vec<HistoryEventWire>,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct HistoryEventWire {
+    amount: f64,
+    date: DateTimeUtcWire,
+    #[serde(flatten)]
+    details: HistoryEventDetailsWire,
+}
+
+/// The type-specific payload of a history event, stored under a key named after its `type`.
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HistoryEventDetailsWire {
+    Trade { trade: TradeHistoryEventWire },
+    Option { option: OptionHistoryEventWire },
+    Ach { ach: CashHistoryEventWire },
+    Dividend { dividend: CashHistoryEventWire },
+    Fee { fee: CashHistoryEventWire },
+    Journal { journal: CashHistoryEventWire },
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct TradeHistoryEventWire {
+    commission: f64,
+    description: String,
+    price: f64,
+    quantity: f64,
+    symbol: String,
+    trade_type: String,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OptionHistoryEventWire {
+    option_type: String,
+    description: String,
+    quantity: f64,
+}
+
+#[derive(Clone, Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct CashHistoryEventWire {
+    description: String,
+    quantity: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<String>,
 }
@@ -1,11 +1,15 @@
 use std::str::FromStr;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 
 use crate::common::AccountType;
+use crate::trading::types::{
+    NetPriceType, OptionSide, OptionSymbol, OrderClass, OrderDuration, OrderSide, OrderType,
+};
+use crate::utils::deserializers::{envelope, vec_from_one_or_many, EnvelopeItem};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AccountNumber(String);
 
 impl FromStr for AccountNumber {
@@ -35,6 +39,10 @@ impl Page {
     pub fn new(page_number: i32) -> Self {
         Self(page_number)
     }
+
+    pub fn number(&self) -> i32 {
+        self.0
+    }
 }
 
 impl std::default::Default for Page {
@@ -82,7 +90,7 @@ impl From<u32> for Limit {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum EventType {
     Trade,
@@ -96,6 +104,7 @@ pub enum EventType {
     Check,
     Transfer,
     Adjustment,
+    Interest,
 }
 
 impl std::fmt::Display for EventType {
@@ -112,6 +121,7 @@ impl std::fmt::Display for EventType {
             EventType::Check => "check",
             EventType::Transfer => "transfer",
             EventType::Adjustment => "adjustment",
+            EventType::Interest => "interest",
         };
         f.write_str(value)
     }
@@ -121,12 +131,69 @@ pub struct GetAccountBalancesResponse {
     balances: AccountBalances,
 }
 
+impl GetAccountBalancesResponse {
+    pub fn balances(&self) -> &AccountBalances {
+        &self.balances
+    }
+}
+
+/// Balances of an account. Tradier reports a different set of buying power fields depending on
+/// the account type, so the variant is selected from the `account_type` field.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "account_type", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum AccountBalances {
+    Cash(CashBalances),
+    Margin(MarginBalances),
+    Pdt(PdtBalances),
+}
+
+impl AccountBalances {
+    pub fn account_type(&self) -> AccountType {
+        match self {
+            AccountBalances::Cash(_) => AccountType::Cash,
+            AccountBalances::Margin(_) => AccountType::Margin,
+            AccountBalances::Pdt(_) => AccountType::Pdt,
+        }
+    }
+
+    /// The fields reported for every account type.
+    pub fn summary(&self) -> &BalanceSummary {
+        match self {
+            AccountBalances::Cash(balances) => balances.summary(),
+            AccountBalances::Margin(balances) => balances.summary(),
+            AccountBalances::Pdt(balances) => balances.summary(),
+        }
+    }
+
+    pub fn as_cash(&self) -> Option<&CashBalances> {
+        match self {
+            AccountBalances::Cash(balances) => Some(balances),
+            _ => None,
+        }
+    }
+
+    pub fn as_margin(&self) -> Option<&MarginBalances> {
+        match self {
+            AccountBalances::Margin(balances) => Some(balances),
+            _ => None,
+        }
+    }
+
+    pub fn as_pdt(&self) -> Option<&PdtBalances> {
+        match self {
+            AccountBalances::Pdt(balances) => Some(balances),
+            _ => None,
+        }
+    }
+}
+
+/// Balance fields shared by cash, margin and pattern day trader accounts.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
-pub struct AccountBalances {
+pub struct BalanceSummary {
     option_short_value: f64,
     total_equity: f64,
     account_number: String,
-    account_type: AccountType,
     close_pl: f64,
     current_requirement: f64,
     equity: f64,
@@ -141,11 +208,123 @@ pub struct AccountBalances {
     total_cash: f64,
     uncleared_funds: f64,
     pending_cash: f64,
-    margin: Margin,
 }
 
+impl BalanceSummary {
+    pub fn option_short_value(&self) -> f64 {
+        self.option_short_value
+    }
+
+    pub fn total_equity(&self) -> f64 {
+        self.total_equity
+    }
+
+    pub fn account_number(&self) -> &str {
+        &self.account_number
+    }
+
+    pub fn close_pl(&self) -> f64 {
+        self.close_pl
+    }
+
+    pub fn current_requirement(&self) -> f64 {
+        self.current_requirement
+    }
+
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    pub fn long_market_value(&self) -> f64 {
+        self.long_market_value
+    }
+
+    pub fn market_value(&self) -> f64 {
+        self.market_value
+    }
+
+    pub fn open_pl(&self) -> f64 {
+        self.open_pl
+    }
+
+    pub fn option_long_value(&self) -> f64 {
+        self.option_long_value
+    }
+
+    pub fn option_requirement(&self) -> f64 {
+        self.option_requirement
+    }
+
+    pub fn pending_orders_count(&self) -> i32 {
+        self.pending_orders_count
+    }
+
+    pub fn short_market_value(&self) -> f64 {
+        self.short_market_value
+    }
+
+    pub fn stock_long_value(&self) -> f64 {
+        self.stock_long_value
+    }
+
+    pub fn total_cash(&self) -> f64 {
+        self.total_cash
+    }
+
+    pub fn uncleared_funds(&self) -> f64 {
+        self.uncleared_funds
+    }
+
+    pub fn pending_cash(&self) -> f64 {
+        self.pending_cash
+    }
+}
+
+/// Balances of a cash account.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CashBalances {
+    #[serde(flatten)]
+    summary: BalanceSummary,
+    cash: CashDetails,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+struct CashDetails {
+    cash_available: f64,
+    sweep: f64,
+    unsettled_funds: f64,
+}
+
+impl CashBalances {
+    pub fn summary(&self) -> &BalanceSummary {
+        &self.summary
+    }
+
+    /// Settled cash that can be used to open new positions.
+    pub fn cash_available(&self) -> f64 {
+        self.cash.cash_available
+    }
+
+    pub fn sweep(&self) -> f64 {
+        self.cash.sweep
+    }
+
+    /// Proceeds of recent sales that have not settled yet.
+    pub fn unsettled_funds(&self) -> f64 {
+        self.cash.unsettled_funds
+    }
+}
+
+/// Balances of a margin account.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
-pub struct Margin {
+pub struct MarginBalances {
+    #[serde(flatten)]
+    summary: BalanceSummary,
+    margin: MarginDetails,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+struct MarginDetails {
     fed_call: f64,
     maintenance_call: f64,
     option_buying_power: f64,
@@ -154,6 +333,86 @@ pub struct Margin {
     sweep: f64,
 }
 
+impl MarginBalances {
+    pub fn summary(&self) -> &BalanceSummary {
+        &self.summary
+    }
+
+    pub fn fed_call(&self) -> f64 {
+        self.margin.fed_call
+    }
+
+    pub fn maintenance_call(&self) -> f64 {
+        self.margin.maintenance_call
+    }
+
+    pub fn option_buying_power(&self) -> f64 {
+        self.margin.option_buying_power
+    }
+
+    pub fn stock_buying_power(&self) -> f64 {
+        self.margin.stock_buying_power
+    }
+
+    pub fn stock_short_value(&self) -> f64 {
+        self.margin.stock_short_value
+    }
+
+    pub fn sweep(&self) -> f64 {
+        self.margin.sweep
+    }
+}
+
+/// Balances of a pattern day trader account.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PdtBalances {
+    #[serde(flatten)]
+    summary: BalanceSummary,
+    pdt: PdtDetails,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+struct PdtDetails {
+    fed_call: f64,
+    maintenance_call: f64,
+    option_buying_power: f64,
+    stock_buying_power: f64,
+    stock_short_value: f64,
+    #[serde(default)]
+    day_trade_buying_power: Option<f64>,
+}
+
+impl PdtBalances {
+    pub fn summary(&self) -> &BalanceSummary {
+        &self.summary
+    }
+
+    pub fn fed_call(&self) -> f64 {
+        self.pdt.fed_call
+    }
+
+    pub fn maintenance_call(&self) -> f64 {
+        self.pdt.maintenance_call
+    }
+
+    pub fn option_buying_power(&self) -> f64 {
+        self.pdt.option_buying_power
+    }
+
+    pub fn stock_buying_power(&self) -> f64 {
+        self.pdt.stock_buying_power
+    }
+
+    pub fn stock_short_value(&self) -> f64 {
+        self.pdt.stock_short_value
+    }
+
+    /// Intraday buying power, when Tradier reports it.
+    pub fn day_trade_buying_power(&self) -> Option<f64> {
+        self.pdt.day_trade_buying_power
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Position {
     cost_basis: f64,
@@ -163,21 +422,743 @@ pub struct Position {
     symbol: String,
 }
 
+impl Position {
+    pub fn cost_basis(&self) -> f64 {
+        self.cost_basis
+    }
+
+    pub fn date_acquired(&self) -> DateTime<Utc> {
+        self.date_acquired
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+}
+
+impl EnvelopeItem for Position {
+    const KEY: &'static str = "position";
+}
+
+/// Open positions of an account, unwrapped from Tradier's `positions.position` envelope.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct GetAccountPositionsResponse {
+    #[serde(deserialize_with = "envelope")]
     positions: Vec<Position>,
 }
 
+impl GetAccountPositionsResponse {
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+}
+
+/// The lifecycle state of an order.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OrderStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Expired,
+    Canceled,
+    Pending,
+    Rejected,
+    Error,
+    Calculated,
+    AcceptedForBidding,
+    Held,
+}
+
+impl OrderStatus {
+    /// Returns `true` if the order can no longer fill.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled
+                | OrderStatus::Expired
+                | OrderStatus::Canceled
+                | OrderStatus::Rejected
+                | OrderStatus::Error
+        )
+    }
+}
+
+/// The side of an existing order, which is an equity side for equity legs and an option side
+/// for option legs.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum TradeSide {
+    Equity(OrderSide),
+    Option(OptionSide),
+}
+
+/// The pricing type of an existing order: a regular order type for single-leg orders and a net
+/// price type for multileg and combo orders.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PriceType {
+    Order(OrderType),
+    Net(NetPriceType),
+}
+
+/// An order as reported by Tradier.
+///
+/// Multileg, combo and advanced (OTO, OCO, OTOCO) orders carry their component orders in
+/// [`legs`](Order::legs); for every other class it is empty.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Order {
+    id: u64,
+    #[serde(rename = "type")]
+    order_type: PriceType,
+    symbol: String,
+    #[serde(default)]
+    side: Option<TradeSide>,
+    #[serde(default)]
+    quantity: f64,
+    status: OrderStatus,
+    #[serde(default)]
+    duration: Option<OrderDuration>,
+    #[serde(default)]
+    price: Option<f64>,
+    #[serde(default)]
+    stop: Option<f64>,
+    #[serde(default)]
+    avg_fill_price: f64,
+    #[serde(default)]
+    exec_quantity: f64,
+    #[serde(default)]
+    last_fill_price: f64,
+    #[serde(default)]
+    last_fill_quantity: f64,
+    #[serde(default)]
+    remaining_quantity: f64,
+    create_date: DateTime<Utc>,
+    transaction_date: DateTime<Utc>,
+    class: OrderClass,
+    #[serde(default)]
+    option_symbol: Option<OptionSymbol>,
+    #[serde(default)]
+    strategy: Option<String>,
+    #[serde(default)]
+    num_legs: Option<u32>,
+    #[serde(default)]
+    tag: Option<String>,
+    /// Explanation provided by Tradier when an order is rejected.
+    #[serde(default)]
+    reason_description: Option<String>,
+    #[serde(rename = "leg", default, deserialize_with = "vec_from_one_or_many")]
+    legs: Vec<Order>,
+}
+
+impl Order {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn order_type(&self) -> PriceType {
+        self.order_type
+    }
+
+    /// The traded symbol, or the underlying root for option orders.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The side of the order; `None` for multileg and combo orders, whose legs carry it.
+    pub fn side(&self) -> Option<TradeSide> {
+        self.side
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        self.status
+    }
+
+    pub fn duration(&self) -> Option<OrderDuration> {
+        self.duration
+    }
+
+    pub fn price(&self) -> Option<f64> {
+        self.price
+    }
+
+    pub fn stop(&self) -> Option<f64> {
+        self.stop
+    }
+
+    pub fn avg_fill_price(&self) -> f64 {
+        self.avg_fill_price
+    }
+
+    pub fn exec_quantity(&self) -> f64 {
+        self.exec_quantity
+    }
+
+    pub fn last_fill_price(&self) -> f64 {
+        self.last_fill_price
+    }
+
+    pub fn last_fill_quantity(&self) -> f64 {
+        self.last_fill_quantity
+    }
+
+    pub fn remaining_quantity(&self) -> f64 {
+        self.remaining_quantity
+    }
+
+    pub fn create_date(&self) -> DateTime<Utc> {
+        self.create_date
+    }
+
+    pub fn transaction_date(&self) -> DateTime<Utc> {
+        self.transaction_date
+    }
+
+    pub fn class(&self) -> OrderClass {
+        self.class
+    }
+
+    pub fn option_symbol(&self) -> Option<&OptionSymbol> {
+        self.option_symbol.as_ref()
+    }
+
+    pub fn strategy(&self) -> Option<&str> {
+        self.strategy.as_deref()
+    }
+
+    pub fn num_legs(&self) -> Option<u32> {
+        self.num_legs
+    }
+
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// Explanation provided by Tradier when an order is rejected.
+    pub fn reason_description(&self) -> Option<&str> {
+        self.reason_description.as_deref()
+    }
+
+    /// The component orders of multileg, combo and advanced orders.
+    pub fn legs(&self) -> &[Order] {
+        &self.legs
+    }
+}
+
+impl EnvelopeItem for Order {
+    const KEY: &'static str = "order";
+}
+
+/// Orders of an account, unwrapped from Tradier's `orders.order` envelope.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct GetAccountOrdersResponse {
+    #[serde(deserialize_with = "envelope")]
+    orders: Vec<Order>,
+}
+
+impl GetAccountOrdersResponse {
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+}
+
+/// A single order of an account, unwrapped from Tradier's `order` envelope.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct GetAccountOrderResponse {
+    order: Order,
+}
+
+impl GetAccountOrderResponse {
+    pub fn order(&self) -> &Order {
+        &self.order
+    }
+}
+
+/// Filters for [`get_account_history`](crate::non_blocking::operation::Accounts::get_account_history).
+///
+/// Every filter is optional; an empty query returns the first page of all events.
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use tradier::types::{AccountHistoryQuery, EventType, Limit};
+///
+/// let query = AccountHistoryQuery::builder()
+///     .limit(Limit::new(100))
+///     .event_type(EventType::Trade)
+///     .start(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+///     .symbol("SPY")
+///     .build();
+/// # let _ = query;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccountHistoryQuery {
+    page: Option<Page>,
+    limit: Option<Limit>,
+    event_type: Option<EventType>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    symbol: Option<String>,
+}
+
+#[bon::bon]
+impl AccountHistoryQuery {
+    /// Constructs a new `AccountHistoryQuery`.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        page: Option<Page>,
+        limit: Option<Limit>,
+        event_type: Option<EventType>,
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+        #[builder(into)] symbol: Option<String>,
+    ) -> Self {
+        AccountHistoryQuery {
+            page,
+            limit,
+            event_type,
+            start,
+            end,
+            symbol,
+        }
+    }
+
+    /// The page a paginated walk over this query starts from.
+    pub(crate) fn first_page(&self) -> Page {
+        self.page.clone().unwrap_or_default()
+    }
+
+    /// Returns a copy of this query targeting `page`.
+    pub(crate) fn with_page(&self, page: Page) -> Self {
+        AccountHistoryQuery {
+            page: Some(page),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(page) = &self.page {
+            pairs.push(("page", page.to_string()));
+        }
+        if let Some(limit) = &self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(event_type) = &self.event_type {
+            pairs.push(("type", event_type.to_string()));
+        }
+        if let Some(start) = &self.start {
+            pairs.push(("start", start.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(end) = &self.end {
+            pairs.push(("end", end.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(symbol) = &self.symbol {
+            pairs.push(("symbol", symbol.clone()));
+        }
+        pairs
+    }
+}
+
+/// An entry in the account history.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct HistoryEvent {
+    amount: f64,
+    date: DateTime<Utc>,
+    #[serde(flatten)]
+    details: HistoryEventDetails,
+}
+
+impl HistoryEvent {
+    /// Net cash effect of the event on the account.
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn date(&self) -> DateTime<Utc> {
+        self.date
+    }
+
+    /// Type-specific payload, taken from the field named after the event's `type`.
+    pub fn details(&self) -> &HistoryEventDetails {
+        &self.details
+    }
+
+    /// Returns the type of the event.
+    pub fn event_type(&self) -> EventType {
+        self.details.event_type()
+    }
+}
+
+/// Payload of a [`HistoryEvent`], keyed on the event type.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum HistoryEventDetails {
+    Trade(TradeHistoryEvent),
+    Option(OptionHistoryEvent),
+    Ach(CashHistoryEvent),
+    Wire(CashHistoryEvent),
+    Dividend(CashHistoryEvent),
+    Fee(CashHistoryEvent),
+    Tax(CashHistoryEvent),
+    Journal(CashHistoryEvent),
+    Check(CashHistoryEvent),
+    Transfer(CashHistoryEvent),
+    Adjustment(CashHistoryEvent),
+    Interest(CashHistoryEvent),
+}
+
+impl HistoryEventDetails {
+    /// Returns the type of the event this payload belongs to.
+    pub fn event_type(&self) -> EventType {
+        match self {
+            HistoryEventDetails::Trade(_) => EventType::Trade,
+            HistoryEventDetails::Option(_) => EventType::Option,
+            HistoryEventDetails::Ach(_) => EventType::Ach,
+            HistoryEventDetails::Wire(_) => EventType::Wire,
+            HistoryEventDetails::Dividend(_) => EventType::Dividend,
+            HistoryEventDetails::Fee(_) => EventType::Fee,
+            HistoryEventDetails::Tax(_) => EventType::Tax,
+            HistoryEventDetails::Journal(_) => EventType::Journal,
+            HistoryEventDetails::Check(_) => EventType::Check,
+            HistoryEventDetails::Transfer(_) => EventType::Transfer,
+            HistoryEventDetails::Adjustment(_) => EventType::Adjustment,
+            HistoryEventDetails::Interest(_) => EventType::Interest,
+        }
+    }
+}
+
+/// Payload of a `trade` history event.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct TradeHistoryEvent {
+    #[serde(default)]
+    commission: f64,
+    description: String,
+    price: f64,
+    quantity: f64,
+    symbol: String,
+    trade_type: String,
+}
+
+impl TradeHistoryEvent {
+    pub fn commission(&self) -> f64 {
+        self.commission
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The instrument class traded, e.g. `Equity` or `Option`.
+    pub fn trade_type(&self) -> &str {
+        &self.trade_type
+    }
+}
+
+/// Payload of an `option` history event, such as an expiration, assignment or exercise.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct OptionHistoryEvent {
+    option_type: String,
+    description: String,
+    quantity: f64,
+}
+
+impl OptionHistoryEvent {
+    /// Tradier's code for the option event, e.g. `OPTEXP` for an expiration.
+    pub fn option_type(&self) -> &str {
+        &self.option_type
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+}
+
+/// Payload shared by cash movements and other non-trade history events.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CashHistoryEvent {
+    description: String,
+    #[serde(default)]
+    quantity: f64,
+    #[serde(default)]
+    symbol: Option<String>,
+}
+
+impl CashHistoryEvent {
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    pub fn symbol(&self) -> Option<&str> {
+        self.symbol.as_deref()
+    }
+}
+
+impl EnvelopeItem for HistoryEvent {
+    const KEY: &'static str = "event";
+}
+
+/// One page of account history, unwrapped from Tradier's `history.event` envelope.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct GetAccountHistoryResponse {
+    #[serde(deserialize_with = "envelope")]
+    history: Vec<HistoryEvent>,
+}
+
+impl GetAccountHistoryResponse {
+    pub fn history(&self) -> &[HistoryEvent] {
+        &self.history
+    }
+
+    pub(crate) fn into_history(self) -> Vec<HistoryEvent> {
+        self.history
+    }
+}
+
+/// Field used to order closed positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GainLossSortBy {
+    OpenDate,
+    CloseDate,
+}
+
+impl std::fmt::Display for GainLossSortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            GainLossSortBy::OpenDate => "openDate",
+            GainLossSortBy::CloseDate => "closeDate",
+        };
+        f.write_str(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl std::fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        };
+        f.write_str(value)
+    }
+}
+
+/// Filters and ordering for
+/// [`get_account_gainloss`](crate::non_blocking::operation::Accounts::get_account_gainloss).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GainLossQuery {
+    page: Option<Page>,
+    limit: Option<Limit>,
+    sort_by: Option<GainLossSortBy>,
+    sort: Option<SortDirection>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    symbol: Option<String>,
+}
+
+#[bon::bon]
+impl GainLossQuery {
+    /// Constructs a new `GainLossQuery`.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        page: Option<Page>,
+        limit: Option<Limit>,
+        sort_by: Option<GainLossSortBy>,
+        sort: Option<SortDirection>,
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+        #[builder(into)] symbol: Option<String>,
+    ) -> Self {
+        GainLossQuery {
+            page,
+            limit,
+            sort_by,
+            sort,
+            start,
+            end,
+            symbol,
+        }
+    }
+
+    /// The page a paginated walk over this query starts from.
+    pub(crate) fn first_page(&self) -> Page {
+        self.page.clone().unwrap_or_default()
+    }
+
+    /// Returns a copy of this query targeting `page`.
+    pub(crate) fn with_page(&self, page: Page) -> Self {
+        GainLossQuery {
+            page: Some(page),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(page) = &self.page {
+            pairs.push(("page", page.to_string()));
+        }
+        if let Some(limit) = &self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(sort_by) = &self.sort_by {
+            pairs.push(("sortBy", sort_by.to_string()));
+        }
+        if let Some(sort) = &self.sort {
+            pairs.push(("sort", sort.to_string()));
+        }
+        if let Some(start) = &self.start {
+            pairs.push(("start", start.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(end) = &self.end {
+            pairs.push(("end", end.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(symbol) = &self.symbol {
+            pairs.push(("symbol", symbol.clone()));
+        }
+        pairs
+    }
+}
+
+/// A closed position and its realized gain or loss.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ClosedPosition {
+    symbol: String,
+    quantity: f64,
+    cost: f64,
+    proceeds: f64,
+    gain_loss: f64,
+    gain_loss_percent: f64,
+    open_date: DateTime<Utc>,
+    close_date: DateTime<Utc>,
+    term: u32,
+}
+
+impl ClosedPosition {
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.quantity
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+
+    pub fn proceeds(&self) -> f64 {
+        self.proceeds
+    }
+
+    pub fn gain_loss(&self) -> f64 {
+        self.gain_loss
+    }
+
+    pub fn gain_loss_percent(&self) -> f64 {
+        self.gain_loss_percent
+    }
+
+    pub fn open_date(&self) -> DateTime<Utc> {
+        self.open_date
+    }
+
+    pub fn close_date(&self) -> DateTime<Utc> {
+        self.close_date
+    }
+
+    /// Number of days the position was held.
+    pub fn term(&self) -> u32 {
+        self.term
+    }
+
+    /// Returns `true` if the position was held for more than a year.
+    pub fn is_long_term(&self) -> bool {
+        self.term > 365
+    }
+}
+
+impl EnvelopeItem for ClosedPosition {
+    const KEY: &'static str = "closed_position";
+}
+
+/// One page of closed positions, unwrapped from Tradier's `gainloss.closed_position` envelope.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct GetAccountGainLossResponse {
+    #[serde(deserialize_with = "envelope")]
+    gainloss: Vec<ClosedPosition>,
+}
+
+impl GetAccountGainLossResponse {
+    pub fn gainloss(&self) -> &[ClosedPosition] {
+        &self.gainloss
+    }
+
+    pub(crate) fn into_gainloss(self) -> Vec<ClosedPosition> {
+        self.gainloss
+    }
+}
+
 #[cfg(test)]
 mod test {
     use proptest::prelude::*;
 
+    use chrono::NaiveDate;
+
     use super::{
-        AccountNumber, EventType, GetAccountBalancesResponse, GetAccountPositionsResponse, Limit,
-        Page,
+        AccountHistoryQuery, AccountNumber, AccountType, EventType, GainLossQuery, GainLossSortBy,
+        GetAccountBalancesResponse, GetAccountGainLossResponse, GetAccountHistoryResponse,
+        GetAccountOrderResponse, GetAccountOrdersResponse, GetAccountPositionsResponse,
+        HistoryEvent, HistoryEventDetails, Limit, NetPriceType, OptionSide, OrderClass, OrderSide,
+        OrderStatus, OrderType, Page, PriceType, SortDirection, TradeSide,
     };
     use crate::{
-        accounts::test_support::{GetAccountBalancesResponseWire, GetAccountPositionsResponseWire},
+        accounts::test_support::{
+            ClosedPositionWire, GetAccountBalancesResponseWire, GetAccountGainLossResponseWire,
+            GetAccountOrdersResponseWire, GetAccountPositionsResponseWire, HistoryEventWire,
+            OrderWire, PositionWire,
+        },
+        utils::tests::{envelope_json, EnvelopeShape},
         Result,
     };
 
@@ -225,6 +1206,359 @@ mod test {
             let result: std::result::Result<GetAccountPositionsResponse, serde_json::Error> = serde_json::from_str(&response);
             assert!(result.is_ok());
         }
+
+        #[test]
+        fn test_deserialize_orders_from_json(response in any::<GetAccountOrdersResponseWire>()) {
+            let response = serde_json::to_string_pretty(&response)
+                .expect("test fixture to serialize");
+            let result: std::result::Result<GetAccountOrdersResponse, serde_json::Error> = serde_json::from_str(&response);
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn test_deserialize_positions_envelope_shapes(
+            positions in prop::collection::vec(any::<PositionWire>(), 0..4),
+            shape in any::<EnvelopeShape>(),
+        ) {
+            let (positions, expected) = envelope_json("position", &positions, shape);
+            let response: GetAccountPositionsResponse =
+                serde_json::from_value(serde_json::json!({ "positions": positions }))
+                    .expect("positions envelope to deserialize");
+            prop_assert_eq!(response.positions().len(), expected);
+        }
+
+        #[test]
+        fn test_deserialize_orders_envelope_shapes(
+            orders in prop::collection::vec(any::<OrderWire>(), 0..4),
+            shape in any::<EnvelopeShape>(),
+        ) {
+            let (orders, expected) = envelope_json("order", &orders, shape);
+            let response: GetAccountOrdersResponse =
+                serde_json::from_value(serde_json::json!({ "orders": orders }))
+                    .expect("orders envelope to deserialize");
+            prop_assert_eq!(response.orders.len(), expected);
+        }
+
+        #[test]
+        fn test_deserialize_history_envelope_shapes(
+            events in prop::collection::vec(any::<HistoryEventWire>(), 0..4),
+            shape in any::<EnvelopeShape>(),
+        ) {
+            let (events, expected) = envelope_json("event", &events, shape);
+            let response: GetAccountHistoryResponse =
+                serde_json::from_value(serde_json::json!({ "history": events }))
+                    .expect("history envelope to deserialize");
+            prop_assert_eq!(response.history.len(), expected);
+        }
+
+        #[test]
+        fn test_deserialize_gainloss_envelope_shapes(
+            positions in prop::collection::vec(any::<ClosedPositionWire>(), 0..4),
+            shape in any::<EnvelopeShape>(),
+        ) {
+            let (positions, expected) = envelope_json("closed_position", &positions, shape);
+            let response: GetAccountGainLossResponse =
+                serde_json::from_value(serde_json::json!({ "gainloss": positions }))
+                    .expect("gainloss envelope to deserialize");
+            prop_assert_eq!(response.gainloss.len(), expected);
+        }
+
+        #[test]
+        fn test_deserialize_gainloss_from_json(response in any::<GetAccountGainLossResponseWire>()) {
+            let response = serde_json::to_string_pretty(&response)
+                .expect("test fixture to serialize");
+            let result: std::result::Result<GetAccountGainLossResponse, serde_json::Error> = serde_json::from_str(&response);
+            assert!(result.is_ok());
+        }
+    }
+
+    const MULTILEG_ORDER: &str = r#"{
+        "order": {
+            "id": 229063,
+            "type": "debit",
+            "symbol": "SPY",
+            "side": "buy",
+            "quantity": 1.0,
+            "status": "canceled",
+            "duration": "pre",
+            "price": 42.0,
+            "avg_fill_price": 0.0,
+            "exec_quantity": 0.0,
+            "last_fill_price": 0.0,
+            "last_fill_quantity": 0.0,
+            "remaining_quantity": 0.0,
+            "create_date": "2018-06-12T21:13:36.076Z",
+            "transaction_date": "2018-06-12T21:18:41.604Z",
+            "class": "multileg",
+            "num_legs": 2,
+            "strategy": "spread",
+            "tag": "my-spread",
+            "leg": [
+                {
+                    "id": 229064,
+                    "type": "debit",
+                    "symbol": "SPY",
+                    "side": "buy_to_open",
+                    "quantity": 1.0,
+                    "status": "canceled",
+                    "duration": "pre",
+                    "price": 42.0,
+                    "avg_fill_price": 0.0,
+                    "exec_quantity": 0.0,
+                    "last_fill_price": 0.0,
+                    "last_fill_quantity": 0.0,
+                    "remaining_quantity": 0.0,
+                    "create_date": "2018-06-12T21:13:36.076Z",
+                    "transaction_date": "2018-06-12T21:18:41.587Z",
+                    "class": "option",
+                    "option_symbol": "SPY180720C00274000"
+                },
+                {
+                    "id": 229065,
+                    "type": "debit",
+                    "symbol": "SPY",
+                    "side": "sell_to_open",
+                    "quantity": 1.0,
+                    "status": "canceled",
+                    "duration": "pre",
+                    "price": 42.0,
+                    "avg_fill_price": 0.0,
+                    "exec_quantity": 0.0,
+                    "last_fill_price": 0.0,
+                    "last_fill_quantity": 0.0,
+                    "remaining_quantity": 0.0,
+                    "create_date": "2018-06-12T21:13:36.076Z",
+                    "transaction_date": "2018-06-12T21:18:41.597Z",
+                    "class": "option",
+                    "option_symbol": "SPY180720C00275000"
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_deserialize_multileg_order_with_legs() {
+        let order = serde_json::from_str::<GetAccountOrderResponse>(MULTILEG_ORDER)
+            .expect("order to deserialize")
+            .order;
+        assert_eq!(order.class, OrderClass::Multileg);
+        assert_eq!(order.order_type, PriceType::Net(NetPriceType::Debit));
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert!(order.status.is_final());
+        assert_eq!(order.tag.as_deref(), Some("my-spread"));
+        assert_eq!(order.legs.len(), 2);
+        assert_eq!(
+            order.legs[1].side,
+            Some(TradeSide::Option(OptionSide::SellToOpen))
+        );
+        assert_eq!(
+            order.legs[0]
+                .option_symbol
+                .as_ref()
+                .map(|symbol| symbol.strike()),
+            Some(274.0)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_single_order_list() {
+        let json = r#"{"orders":{"order":{"id":228175,"type":"limit","symbol":"AAPL","side":"buy","quantity":50.0,"status":"partially_filled","duration":"pre","price":22.0,"avg_fill_price":21.5,"exec_quantity":20.0,"last_fill_price":21.5,"last_fill_quantity":20.0,"remaining_quantity":30.0,"create_date":"2018-06-01T12:02:29.682Z","transaction_date":"2018-06-01T12:30:02.385Z","class":"equity"}}}"#;
+        let orders = serde_json::from_str::<GetAccountOrdersResponse>(json)
+            .expect("orders to deserialize")
+            .orders;
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0];
+        assert_eq!(order.order_type, PriceType::Order(OrderType::Limit));
+        assert_eq!(order.side, Some(TradeSide::Equity(OrderSide::Buy)));
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.exec_quantity, 20.0);
+        assert_eq!(order.avg_fill_price, 21.5);
+        assert!(order.legs.is_empty());
+    }
+
+    const HISTORY: &str = r#"{
+        "history": {
+            "event": [
+                {
+                    "amount": -3000.0,
+                    "date": "2018-05-23T00:00:00Z",
+                    "type": "journal",
+                    "journal": {"description": "6YA-00005 TO 6YA-00102", "quantity": 0.0}
+                },
+                {
+                    "amount": -8.74,
+                    "date": "2018-05-29T00:00:00Z",
+                    "type": "trade",
+                    "trade": {
+                        "commission": 0.0,
+                        "description": "SPDR S&P 500",
+                        "price": 8.74,
+                        "quantity": 1.0,
+                        "symbol": "SPY",
+                        "trade_type": "Equity"
+                    }
+                },
+                {
+                    "amount": 0.0,
+                    "date": "2018-06-15T00:00:00Z",
+                    "type": "option",
+                    "option": {"option_type": "OPTEXP", "description": "Expired", "quantity": -1.0}
+                },
+                {
+                    "amount": 12.5,
+                    "date": "2018-06-29T00:00:00Z",
+                    "type": "dividend",
+                    "dividend": {"description": "ORDINARY DIVIDEND", "quantity": 0.0, "symbol": "AAPL"}
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_deserialize_history_event_payloads() {
+        let events = serde_json::from_str::<GetAccountHistoryResponse>(HISTORY)
+            .expect("history to deserialize")
+            .history;
+        let types: Vec<EventType> = events.iter().map(HistoryEvent::event_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                EventType::Journal,
+                EventType::Trade,
+                EventType::Option,
+                EventType::Dividend
+            ]
+        );
+        let HistoryEventDetails::Trade(trade) = &events[1].details else {
+            panic!("expected a trade, got {:?}", events[1].details);
+        };
+        assert_eq!(trade.symbol, "SPY");
+        assert_eq!(trade.price, 8.74);
+        assert_eq!(events[1].amount, -8.74);
+        let HistoryEventDetails::Dividend(dividend) = &events[3].details else {
+            panic!("expected a dividend, got {:?}", events[3].details);
+        };
+        assert_eq!(dividend.symbol.as_deref(), Some("AAPL"));
+    }
+
+    #[test]
+    fn test_account_history_query_pairs() {
+        let query = AccountHistoryQuery::builder()
+            .page(Page::new(2))
+            .limit(Limit::new(50))
+            .event_type(EventType::Dividend)
+            .start(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+            .end(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap())
+            .symbol("AAPL")
+            .build();
+        assert_eq!(
+            query.query_pairs(),
+            vec![
+                ("page", "2".to_owned()),
+                ("limit", "50".to_owned()),
+                ("type", "dividend".to_owned()),
+                ("start", "2024-01-01".to_owned()),
+                ("end", "2024-03-31".to_owned()),
+                ("symbol", "AAPL".to_owned()),
+            ]
+        );
+        assert!(AccountHistoryQuery::default().query_pairs().is_empty());
+    }
+
+    #[test]
+    fn test_gainloss_query_pairs() {
+        let query = GainLossQuery::builder()
+            .limit(Limit::new(100))
+            .sort_by(GainLossSortBy::CloseDate)
+            .sort(SortDirection::Desc)
+            .start(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+            .symbol("SNAP")
+            .build();
+        assert_eq!(
+            query.query_pairs(),
+            vec![
+                ("limit", "100".to_owned()),
+                ("sortBy", "closeDate".to_owned()),
+                ("sort", "desc".to_owned()),
+                ("start", "2024-01-01".to_owned()),
+                ("symbol", "SNAP".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_gainloss_past_last_page() {
+        let response = serde_json::from_str::<GetAccountGainLossResponse>(r#"{"gainloss":"null"}"#)
+            .expect("empty page to deserialize");
+        assert!(response.gainloss.is_empty());
+    }
+
+    const CASH_BALANCES: &str = r#"{
+        "balances": {
+            "option_short_value": 0,
+            "total_equity": 17798.36,
+            "account_number": "VA00000000",
+            "account_type": "cash",
+            "close_pl": -4813.77,
+            "current_requirement": 0,
+            "equity": 0,
+            "long_market_value": 16098.36,
+            "market_value": 16098.36,
+            "open_pl": -1014.59,
+            "option_long_value": 0,
+            "option_requirement": 0,
+            "pending_orders_count": 0,
+            "short_market_value": 0,
+            "stock_long_value": 16098.36,
+            "total_cash": 1700.0,
+            "uncleared_funds": 0,
+            "pending_cash": 0,
+            "cash": {
+                "cash_available": 1400.0,
+                "sweep": 0,
+                "unsettled_funds": 300.0
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_deserialize_cash_account_balances() {
+        let response = serde_json::from_str::<GetAccountBalancesResponse>(CASH_BALANCES)
+            .expect("cash balances to deserialize");
+        let balances = response.balances();
+        assert_eq!(balances.account_type(), AccountType::Cash);
+        assert_eq!(balances.summary().account_number(), "VA00000000");
+        assert_eq!(balances.summary().total_cash(), 1700.0);
+        let cash = balances.as_cash().expect("a cash account");
+        assert_eq!(cash.cash_available(), 1400.0);
+        assert_eq!(cash.unsettled_funds(), 300.0);
+        assert!(balances.as_margin().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_pdt_account_balances() {
+        let json = CASH_BALANCES
+            .replace(r#""account_type": "cash""#, r#""account_type": "pdt""#)
+            .replace(
+                r#""cash": {
+                "cash_available": 1400.0,
+                "sweep": 0,
+                "unsettled_funds": 300.0
+            }"#,
+                r#""pdt": {"fed_call": 0, "maintenance_call": 0, "option_buying_power": 6363.86, "stock_buying_power": 6363.86, "stock_short_value": 0, "day_trade_buying_power": 25455.44}"#,
+            );
+        let response = serde_json::from_str::<GetAccountBalancesResponse>(&json)
+            .expect("pdt balances to deserialize");
+        let pdt = response.balances().as_pdt().expect("a pdt account");
+        assert_eq!(pdt.stock_buying_power(), 6363.86);
+        assert_eq!(pdt.day_trade_buying_power(), Some(25455.44));
+    }
+
+    #[test]
+    fn test_margin_balances_require_margin_block() {
+        let json =
+            CASH_BALANCES.replace(r#""account_type": "cash""#, r#""account_type": "margin""#);
+        assert!(serde_json::from_str::<GetAccountBalancesResponse>(&json).is_err());
     }
 
     #[test]
@@ -302,6 +1636,7 @@ mod test {
             (EventType::Check, "check"),
             (EventType::Transfer, "transfer"),
             (EventType::Adjustment, "adjustment"),
+            (EventType::Interest, "interest"),
         ];
 
         for (event, expected) in cases {
@@ -22,11 +22,23 @@
 use tokio::runtime::{Handle, Runtime};
 
 use crate::{
-    accounts::types::{AccountNumber, GetAccountBalancesResponse},
+    accounts::types::{
+        AccountHistoryQuery, AccountNumber, ClosedPosition, GainLossQuery,
+        GetAccountBalancesResponse, GetAccountGainLossResponse, GetAccountHistoryResponse,
+        GetAccountOrderResponse, GetAccountOrdersResponse, HistoryEvent,
+    },
     accounts::{api::blocking::Accounts, api::non_blocking::Accounts as NonBlockingAccounts},
     client::non_blocking::TradierRestClient as AsyncClient,
+    portfolio::{
+        api::{blocking::Portfolio, non_blocking::Portfolio as NonBlockingPortfolio},
+        types::PortfolioSnapshot,
+    },
+    trading::{
+        api::{blocking::Trading, non_blocking::Trading as NonBlockingTrading},
+        types::{OrderModification, OrderPreview, OrderRequest, OrderResponse},
+    },
     user::{api::blocking::User, api::non_blocking::User as NonBlockingUser, UserProfileResponse},
-    utils::Sealed,
+    utils::{PageIter, Sealed},
     Config, Result,
 };
 
@@ -121,6 +133,116 @@ impl Accounts for BlockingTradierRestClient {
         self.runtime
             .block_on(self.rest_client.get_account_positions(account_number))
     }
+
+    fn get_account_orders(
+        &self,
+        account_number: &AccountNumber,
+        include_tags: bool,
+    ) -> Result<GetAccountOrdersResponse> {
+        self.runtime.block_on(
+            self.rest_client
+                .get_account_orders(account_number, include_tags),
+        )
+    }
+
+    fn get_account_order(
+        &self,
+        account_number: &AccountNumber,
+        order_id: u64,
+        include_tags: bool,
+    ) -> Result<GetAccountOrderResponse> {
+        self.runtime.block_on(self.rest_client.get_account_order(
+            account_number,
+            order_id,
+            include_tags,
+        ))
+    }
+
+    fn get_account_history(
+        &self,
+        account_number: &AccountNumber,
+        query: &AccountHistoryQuery,
+    ) -> Result<GetAccountHistoryResponse> {
+        self.runtime
+            .block_on(self.rest_client.get_account_history(account_number, query))
+    }
+
+    fn iter_account_history(
+        &self,
+        account_number: &AccountNumber,
+        query: AccountHistoryQuery,
+        prefetch: usize,
+    ) -> PageIter<'_, HistoryEvent> {
+        PageIter::new(
+            &self.runtime,
+            self.rest_client
+                .stream_account_history(account_number, query, prefetch),
+        )
+    }
+
+    fn get_account_gainloss(
+        &self,
+        account_number: &AccountNumber,
+        query: &GainLossQuery,
+    ) -> Result<GetAccountGainLossResponse> {
+        self.runtime
+            .block_on(self.rest_client.get_account_gainloss(account_number, query))
+    }
+
+    fn iter_account_gainloss(
+        &self,
+        account_number: &AccountNumber,
+        query: GainLossQuery,
+        prefetch: usize,
+    ) -> PageIter<'_, ClosedPosition> {
+        PageIter::new(
+            &self.runtime,
+            self.rest_client
+                .stream_account_gainloss(account_number, query, prefetch),
+        )
+    }
+}
+
+impl Trading for BlockingTradierRestClient {
+    fn place_order<O>(&self, account_number: &AccountNumber, order: &O) -> Result<OrderResponse>
+    where
+        O: OrderRequest + Sync,
+    {
+        self.runtime
+            .block_on(self.rest_client.place_order(account_number, order))
+    }
+
+    fn preview_order<O>(&self, account_number: &AccountNumber, order: &O) -> Result<OrderPreview>
+    where
+        O: OrderRequest + Sync,
+    {
+        self.runtime
+            .block_on(self.rest_client.preview_order(account_number, order))
+    }
+
+    fn modify_order(
+        &self,
+        account_number: &AccountNumber,
+        order_id: u64,
+        modification: &OrderModification,
+    ) -> Result<OrderResponse> {
+        self.runtime.block_on(
+            self.rest_client
+                .modify_order(account_number, order_id, modification),
+        )
+    }
+
+    fn cancel_order(&self, account_number: &AccountNumber, order_id: u64) -> Result<OrderResponse> {
+        self.runtime
+            .block_on(self.rest_client.cancel_order(account_number, order_id))
+    }
+}
+
+impl Portfolio for BlockingTradierRestClient {
+    fn get_portfolio_snapshot(&self) -> Result<PortfolioSnapshot> {
+        self.runtime
+            .block_on(self.rest_client.get_portfolio_snapshot())
+    }
 }
 
 #[cfg(test)]
@@ -129,7 +251,22 @@ mod test {
     use std::cell::RefCell;
 
     use crate::{
-        accounts::test_support::{GetAccountBalancesResponseWire, GetAccountPositionsResponseWire},
+        accounts::test_support::{
+            GetAccountBalancesResponseWire, GetAccountGainLossResponseWire,
+            GetAccountHistoryResponseWire, GetAccountOrderResponseWire,
+            GetAccountOrdersResponseWire, GetAccountPositionsResponseWire, HistoryEventWire,
+        },
+        accounts::types::{
+            AccountHistoryQuery, EventType, GainLossQuery, GainLossSortBy, SortDirection,
+        },
+        trading::{
+            test_support::{OrderPreviewResponseWire, OrderResponseEnvelopeWire},
+            types::{
+                AdvancedLeg, ComboOrder, EquityLeg, EquityOrder, NetPriceType, OptionLeg,
+                OptionOrder, OptionSide, OrderDuration, OrderModification, OrderSide, OrderType,
+                OtocoOrder,
+            },
+        },
         user::test_support::GetUserProfileResponseWire,
         utils::tests::with_env_vars,
         Config,
@@ -227,6 +364,743 @@ mod test {
                 operation.delete();
             });
         });
+
+        // Test GetAccountGainLoss
+
+        proptest!(|(response in any::<GetAccountGainLossResponseWire>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/gainloss"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .query_param("sortBy", "openDate")
+                    .query_param("sort", "asc");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(serde_json::to_vec(&response)
+                        .expect("serialization of wire type for tests to work"));
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let query = GainLossQuery::builder()
+                    .sort_by(GainLossSortBy::OpenDate)
+                    .sort(SortDirection::Asc)
+                    .build();
+                let response = sut.get_account_gainloss(&ascii_string.parse().expect("valid ascii"), &query);
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert!(response.is_ok());
+                operation.delete();
+            });
+        });
+
+        // Test PlaceOrder
+
+        proptest!(|(response in any::<OrderResponseEnvelopeWire>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::POST)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .form_urlencoded_tuple("class", "equity")
+                    .form_urlencoded_tuple("symbol", "AAPL")
+                    .form_urlencoded_tuple("side", "buy")
+                    .form_urlencoded_tuple("quantity", "10")
+                    .form_urlencoded_tuple("type", "limit")
+                    .form_urlencoded_tuple("duration", "day")
+                    .form_urlencoded_tuple("price", "150.50");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(serde_json::to_vec(&response)
+                        .expect("serialization of wire type for tests to work"));
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let order = EquityOrder::builder()
+                    .symbol("AAPL")
+                    .side(OrderSide::Buy)
+                    .quantity(10)
+                    .order_type(OrderType::Limit)
+                    .duration(OrderDuration::Day)
+                    .price(150.5)
+                    .build()
+                    .expect("valid order");
+                let response = sut.place_order(&ascii_string.parse().expect("valid ascii"), &order);
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert!(response.is_ok());
+                operation.delete();
+            });
+        });
+
+        // Test PlaceOrder with an option order
+
+        proptest!(|(response in any::<OrderResponseEnvelopeWire>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::POST)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .form_urlencoded_tuple("class", "option")
+                    .form_urlencoded_tuple("symbol", "SPY")
+                    .form_urlencoded_tuple("option_symbol", "SPY240119P00450000")
+                    .form_urlencoded_tuple("side", "buy_to_open")
+                    .form_urlencoded_tuple("quantity", "1")
+                    .form_urlencoded_tuple("type", "market")
+                    .form_urlencoded_tuple("duration", "day");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let order = OptionOrder::builder()
+                    .option_symbol("SPY240119P00450000".parse().expect("valid OCC symbol"))
+                    .side(OptionSide::BuyToOpen)
+                    .quantity(1)
+                    .order_type(OrderType::Market)
+                    .duration(OrderDuration::Day)
+                    .build()
+                    .expect("valid order");
+                let response = sut
+                    .place_order(&ascii_string.parse().expect("valid ascii"), &order)
+                    .expect("order to be placed");
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert_eq!(response.id, expected["order"]["id"]);
+                assert_eq!(response.status, expected["order"]["status"]);
+                assert_eq!(response.partner_id.as_deref(), expected["order"]["partner_id"].as_str());
+                operation.delete();
+            });
+        });
+
+        // Test PreviewOrder
+
+        proptest!(|(response in any::<OrderPreviewResponseWire>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::POST)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .form_urlencoded_tuple("class", "equity")
+                    .form_urlencoded_tuple("symbol", "SPY")
+                    .form_urlencoded_tuple("preview", "true");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let order = EquityOrder::builder()
+                    .symbol("SPY")
+                    .side(OrderSide::Buy)
+                    .quantity(4)
+                    .order_type(OrderType::Market)
+                    .duration(OrderDuration::Day)
+                    .build()
+                    .expect("valid order");
+                let preview = sut
+                    .preview_order(&ascii_string.parse().expect("valid ascii"), &order)
+                    .expect("order to be previewed");
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                let expected = &expected["order"];
+                assert_eq!(preview.status, expected["status"]);
+                assert_eq!(preview.result, expected["result"]);
+                assert_eq!(preview.margin_change, expected["margin_change"].as_f64());
+                assert_eq!(preview.class.as_deref(), expected["class"].as_str());
+                assert_eq!(preview.warnings.len(), expected["warnings"].as_array().map_or(0, Vec::len));
+                operation.delete();
+            });
+        });
+
+        // Test ModifyOrder
+
+        proptest!(|(response in any::<OrderResponseEnvelopeWire>(),
+                order_id in any::<u64>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::PUT)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders/{order_id}"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .form_urlencoded_tuple("type", "limit")
+                    .form_urlencoded_tuple("price", "151.50");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let modification = OrderModification::builder()
+                    .order_type(OrderType::Limit)
+                    .price(151.5)
+                    .build()
+                    .expect("valid modification");
+                let response = sut
+                    .modify_order(&ascii_string.parse().expect("valid ascii"), order_id, &modification)
+                    .expect("order to be modified");
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert_eq!(response.id, expected["order"]["id"]);
+                assert_eq!(response.status, expected["order"]["status"]);
+                operation.delete();
+            });
+        });
+
+        // Test CancelOrder
+
+        proptest!(|(response in any::<OrderResponseEnvelopeWire>(),
+                order_id in any::<u64>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::DELETE)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders/{order_id}"))).unwrap().path())
+                    .header("accept", "application/json");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let response = sut
+                    .cancel_order(&ascii_string.parse().expect("valid ascii"), order_id)
+                    .expect("order to be canceled");
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert_eq!(response.id, expected["order"]["id"]);
+                assert_eq!(response.status, expected["order"]["status"]);
+                operation.delete();
+            });
+        });
+
+        // Test GetAccountOrders
+
+        proptest!(|(response in any::<GetAccountOrdersResponseWire>(),
+                include_tags in any::<bool>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .query_param("includeTags", include_tags.to_string());
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let orders = sut
+                    .get_account_orders(&ascii_string.parse().expect("valid ascii"), include_tags)
+                    .expect("orders to be listed");
+                let orders = orders.orders();
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                let expected = expected["orders"]["order"].as_array().expect("a list of orders");
+                assert_eq!(orders.len(), expected.len());
+                for (order, expected) in orders.iter().zip(expected) {
+                    assert_eq!(order.id(), expected["id"]);
+                    assert_eq!(order.price(), expected["price"].as_f64());
+                    assert_eq!(order.tag(), expected["tag"].as_str());
+                }
+                operation.delete();
+            });
+        });
+
+        // Test GetAccountOrder
+
+        proptest!(|(response in any::<GetAccountOrderResponseWire>(),
+                order_id in any::<u64>(),
+                include_tags in any::<bool>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/orders/{order_id}"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .query_param("includeTags", include_tags.to_string());
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let order = sut
+                    .get_account_order(&ascii_string.parse().expect("valid ascii"), order_id, include_tags)
+                    .expect("order to be fetched");
+                let order = order.order();
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                assert_eq!(order.id(), expected["order"]["id"]);
+                assert_eq!(order.price(), expected["order"]["price"].as_f64());
+                assert_eq!(order.tag(), expected["order"]["tag"].as_str());
+                operation.delete();
+            });
+        });
+
+        // Test GetAccountHistory
+
+        proptest!(|(response in any::<GetAccountHistoryResponseWire>(),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let body = serde_json::to_vec(&response)
+                .expect("serialization of wire type for tests to work");
+            let expected: serde_json::Value = serde_json::from_slice(&body)
+                .expect("wire type to serialize to JSON");
+            let server = server.borrow_mut();
+            let mut operation = server.mock(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path(url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/history"))).unwrap().path())
+                    .header("accept", "application/json")
+                    .query_param("page", "3")
+                    .query_param("type", "trade")
+                    .query_param("symbol", "SPY");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(body);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let query = AccountHistoryQuery::builder()
+                    .page(3.into())
+                    .event_type(EventType::Trade)
+                    .symbol("SPY")
+                    .build();
+                let events = sut
+                    .get_account_history(&ascii_string.parse().expect("valid ascii"), &query)
+                    .expect("history to be fetched")
+                    .into_history();
+                operation.assert();
+                assert_eq!(operation.calls(), 1);
+                let expected = expected["history"]["event"].as_array().expect("a list of events");
+                assert_eq!(events.len(), expected.len());
+                for (event, expected) in events.iter().zip(expected) {
+                    assert_eq!(event.amount(), expected["amount"]);
+                    assert_eq!(event.event_type().to_string(), expected["type"]);
+                }
+                operation.delete();
+            });
+        });
+
+        // Test IterAccountHistory
+
+        proptest!(|(pages in prop::collection::vec(
+                    prop::collection::vec(any::<HistoryEventWire>(), 1..3), 0..3),
+                ascii_string in prop::collection::vec(0x20u8..0x7fu8, 1..256)
+            .prop_flat_map(|vec| {
+                Just(vec.into_iter().map(|c| c as char).collect::<String>())
+            })
+            .prop_filter("Strings must not be empty or blank", |v| !v.trim().is_empty()))| {
+            let bodies: Vec<Vec<u8>> = pages
+                .iter()
+                .map(|events| serde_json::to_vec(&serde_json::json!({"history": {"event": events}}))
+                    .expect("serialization of wire type for tests to work"))
+                .collect();
+            let expected: Vec<f64> = bodies
+                .iter()
+                .flat_map(|body| {
+                    let page: serde_json::Value = serde_json::from_slice(body)
+                        .expect("wire type to serialize to JSON");
+                    page["history"]["event"]
+                        .as_array()
+                        .expect("a list of events")
+                        .iter()
+                        .map(|event| event["amount"].as_f64().expect("an amount"))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            let server = server.borrow_mut();
+            let path = url::Url::parse(&server.url(format!("/v1/accounts/{ascii_string}/history"))).unwrap().path().to_owned();
+            let mut operations: Vec<_> = bodies.into_iter().enumerate().map(|(index, body)| {
+                server.mock(|when, then| {
+                    when.path(&path)
+                        .header("accept", "application/json")
+                        .query_param("page", (index + 1).to_string());
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(body);
+                })
+            }).collect();
+            operations.push(server.mock(|when, then| {
+                when.path(&path)
+                    .header("accept", "application/json")
+                    .query_param("page", (pages.len() + 1).to_string());
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"history":"null"}"#);
+            }));
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let amounts = sut
+                    .iter_account_history(&ascii_string.parse().expect("valid ascii"), AccountHistoryQuery::default(), 1)
+                    .map(|event| event.expect("page to load").amount())
+                    .collect::<Vec<_>>();
+                for mut operation in operations {
+                    operation.assert();
+                    assert_eq!(operation.calls(), 1);
+                    operation.delete();
+                }
+                assert_eq!(amounts, expected);
+            });
+        });
+
+        // Test GetPortfolioSnapshot
+
+        proptest!(|(balances in any::<GetAccountBalancesResponseWire>(),
+                positions in any::<GetAccountPositionsResponseWire>(),
+                orders in any::<GetAccountOrdersResponseWire>())| {
+            let to_json = |body: &[u8]| -> serde_json::Value {
+                serde_json::from_slice(body).expect("wire type to serialize to JSON")
+            };
+            let balances = serde_json::to_vec(&balances)
+                .expect("serialization of wire type for tests to work");
+            let positions = serde_json::to_vec(&positions)
+                .expect("serialization of wire type for tests to work");
+            let orders = serde_json::to_vec(&orders)
+                .expect("serialization of wire type for tests to work");
+            let expected_equity = to_json(&balances)["balances"]["total_equity"].clone();
+            let expected_positions = to_json(&positions)["positions"]["position"]
+                .as_array()
+                .map_or(0, Vec::len);
+            let expected_open_orders: Vec<serde_json::Value> = to_json(&orders)["orders"]["order"]
+                .as_array()
+                .expect("a list of orders")
+                .iter()
+                .filter(|order| {
+                    !matches!(
+                        order["status"].as_str(),
+                        Some("filled" | "expired" | "canceled" | "rejected" | "error")
+                    )
+                })
+                .cloned()
+                .collect();
+            let server = server.borrow_mut();
+            let account = |number: &str, status: &str| {
+                format!(
+                    r#"{{"account_number":"{number}","classification":"individual","date_created":"2020-01-01T00:00:00.000Z","day_trader":false,"option_level":2,"status":"{status}","type":"cash","last_update_date":"2024-01-01T00:00:00.000Z"}}"#
+                )
+            };
+            let operations = vec![
+                server.mock(|when, then| {
+                    when.path("/v1/user/profile");
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(format!(
+                            r#"{{"profile":{{"id":"id-1","name":"Test","account":[{},{},{}]}}}}"#,
+                            account("VA000001", "active"),
+                            account("VA000002", "active"),
+                            account("VA000003", "closed"),
+                        ));
+                }),
+                server.mock(|when, then| {
+                    when.path("/v1/accounts/VA000001/balances");
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(balances);
+                }),
+                server.mock(|when, then| {
+                    when.path("/v1/accounts/VA000001/positions");
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(positions);
+                }),
+                server.mock(|when, then| {
+                    when.path("/v1/accounts/VA000001/orders");
+                    then.status(200)
+                        .header("content-type", "application/json")
+                        .body(orders);
+                }),
+            ];
+            let mut failing = server.mock(|when, then| {
+                when.path_includes("/v1/accounts/VA000002/");
+                then.status(500).body("Internal Server Error");
+            });
+            let mut closed = server.mock(|when, then| {
+                when.path_includes("/v1/accounts/VA000003/");
+                then.status(200);
+            });
+
+            with_env_vars(vec![("TRADIER_REST_BASE_URL", &server.base_url()),
+            ("TRADIER_ACCESS_TOKEN", "testToken")], || {
+                let config = Config::new();
+                let sut = BlockingTradierRestClient::new(config).expect("client to initialize");
+                let snapshot = sut
+                    .get_portfolio_snapshot()
+                    .expect("snapshot to be built despite a failing account");
+                for mut operation in operations {
+                    operation.assert();
+                    operation.delete();
+                }
+                assert!(failing.calls() >= 1);
+                assert_eq!(closed.calls(), 0);
+                failing.delete();
+                closed.delete();
+
+                assert_eq!(snapshot.accounts().len(), 1);
+                let account = &snapshot.accounts()[0];
+                assert_eq!(account.account_number(), "VA000001");
+                assert_eq!(account.positions().len(), expected_positions);
+                assert_eq!(account.open_orders().len(), expected_open_orders.len());
+                for (order, expected) in account.open_orders().iter().zip(&expected_open_orders) {
+                    assert_eq!(order.id(), expected["id"]);
+                }
+
+                assert_eq!(snapshot.failures().len(), 1);
+                assert_eq!(snapshot.failures()[0].account_number(), "VA000002");
+                assert_eq!(snapshot.totals().total_equity, expected_equity);
+                assert_eq!(snapshot.totals().open_order_count, expected_open_orders.len());
+            });
+        });
+    }
+
+    #[test]
+    fn test_blocking_client_places_combo_order() {
+        let server = MockServer::start();
+        let operation = server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/v1/accounts/VA000001/orders")
+                .form_urlencoded_tuple("class", "combo")
+                .form_urlencoded_tuple("symbol", "AAPL")
+                .form_urlencoded_tuple("type", "debit")
+                .form_urlencoded_tuple("price", "148.50")
+                .form_urlencoded_tuple("side[0]", "buy")
+                .form_urlencoded_tuple("quantity[0]", "100")
+                .form_urlencoded_tuple("option_symbol[1]", "AAPL240119C00155000")
+                .form_urlencoded_tuple("side[1]", "sell_to_open")
+                .form_urlencoded_tuple("quantity[1]", "1");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"order":{"id":257460,"status":"ok"}}"#);
+        });
+
+        with_env_vars(
+            vec![
+                ("TRADIER_REST_BASE_URL", &server.base_url()),
+                ("TRADIER_ACCESS_TOKEN", "testToken"),
+            ],
+            || {
+                let sut =
+                    BlockingTradierRestClient::new(Config::new()).expect("client to initialize");
+                let order = ComboOrder::builder()
+                    .symbol("AAPL")
+                    .price_type(NetPriceType::Debit)
+                    .price(148.5)
+                    .duration(OrderDuration::Day)
+                    .legs(vec![
+                        EquityLeg::new(OrderSide::Buy, 100).into(),
+                        OptionLeg::new(
+                            "AAPL240119C00155000".parse().expect("valid OCC symbol"),
+                            OptionSide::SellToOpen,
+                            1,
+                        )
+                        .into(),
+                    ])
+                    .build()
+                    .expect("valid order");
+                let response = sut
+                    .place_order(&"VA000001".parse().expect("valid ascii"), &order)
+                    .expect("order to be placed");
+                operation.assert();
+                assert_eq!(response.id, 257460);
+                assert_eq!(response.partner_id, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_blocking_client_places_otoco_order() {
+        let server = MockServer::start();
+        let operation = server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/v1/accounts/VA000001/orders")
+                .form_urlencoded_tuple("class", "otoco")
+                .form_urlencoded_tuple("symbol[0]", "SPY")
+                .form_urlencoded_tuple("type[0]", "limit")
+                .form_urlencoded_tuple("price[0]", "500.00")
+                .form_urlencoded_tuple("symbol[1]", "SPY")
+                .form_urlencoded_tuple("price[1]", "520.00")
+                .form_urlencoded_tuple("type[2]", "stop")
+                .form_urlencoded_tuple("stop[2]", "490.00")
+                .form_urlencoded_tuple("duration[2]", "gtc");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"order":{"id":257461,"status":"ok"}}"#);
+        });
+
+        with_env_vars(
+            vec![
+                ("TRADIER_REST_BASE_URL", &server.base_url()),
+                ("TRADIER_ACCESS_TOKEN", "testToken"),
+            ],
+            || {
+                let sut =
+                    BlockingTradierRestClient::new(Config::new()).expect("client to initialize");
+                let leg = |side, order_type, price, stop| {
+                    AdvancedLeg::equity()
+                        .symbol("SPY")
+                        .side(side)
+                        .quantity(5)
+                        .order_type(order_type)
+                        .duration(OrderDuration::Gtc)
+                        .maybe_price(price)
+                        .maybe_stop(stop)
+                        .build()
+                        .expect("valid leg")
+                };
+                let order = OtocoOrder::builder()
+                    .legs([
+                        leg(OrderSide::Buy, OrderType::Limit, Some(500.0), None),
+                        leg(OrderSide::Sell, OrderType::Limit, Some(520.0), None),
+                        leg(OrderSide::Sell, OrderType::Stop, None, Some(490.0)),
+                    ])
+                    .build()
+                    .expect("valid order");
+                let response = sut
+                    .place_order(&"VA000001".parse().expect("valid ascii"), &order)
+                    .expect("order to be placed");
+                operation.assert();
+                assert_eq!(response.id, 257461);
+            },
+        );
+    }
+
+    #[test]
+    fn test_blocking_client_maps_order_state_errors() {
+        let server = MockServer::start();
+        let modify = server.mock(|when, then| {
+            when.method(httpmock::Method::PUT)
+                .path("/v1/accounts/VA000001/orders/257459");
+            then.status(400)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"errors":{"error":["Backend Service failed: order cannot be modified"]}}"#,
+                );
+        });
+        let cancel = server.mock(|when, then| {
+            when.method(httpmock::Method::DELETE)
+                .path("/v1/accounts/VA000001/orders/257459");
+            then.status(400)
+                .header("content-type", "application/json")
+                .body(r#"{"errors":{"error":"Order cannot be canceled"}}"#);
+        });
+
+        with_env_vars(
+            vec![
+                ("TRADIER_REST_BASE_URL", &server.base_url()),
+                ("TRADIER_ACCESS_TOKEN", "testToken"),
+            ],
+            || {
+                let sut =
+                    BlockingTradierRestClient::new(Config::new()).expect("client to initialize");
+                let account_number = "VA000001".parse().expect("valid ascii");
+                let modification = OrderModification::builder()
+                    .duration(OrderDuration::Gtc)
+                    .build()
+                    .expect("valid modification");
+
+                let modified = sut.modify_order(&account_number, 257459, &modification);
+                modify.assert();
+                assert!(matches!(modified, Err(crate::Error::OrderNotModifiable(_))));
+
+                let canceled = sut.cancel_order(&account_number, 257459);
+                cancel.assert();
+                assert!(matches!(canceled, Err(crate::Error::OrderNotCancelable(_))));
+            },
+        );
     }
 
     #[tokio::test]
@@ -3,12 +3,31 @@ use url::Url;
 use crate::{
     accounts::{
         api::non_blocking::Accounts,
-        types::{AccountNumber, GetAccountBalancesResponse},
+        types::{
+            AccountHistoryQuery, AccountNumber, ClosedPosition, GainLossQuery,
+            GetAccountBalancesResponse, GetAccountGainLossResponse, GetAccountHistoryResponse,
+            GetAccountOrderResponse, GetAccountOrdersResponse, HistoryEvent,
+        },
     },
     config::Config,
+    portfolio::{
+        api::non_blocking::Portfolio,
+        types::{AccountFailure, AccountSnapshot, PortfolioSnapshot},
+    },
+    trading::{
+        api::non_blocking::Trading,
+        types::{
+            OrderModification, OrderPreview, OrderPreviewEnvelope, OrderRequest, OrderResponse,
+            OrderResponseEnvelope,
+        },
+    },
     types::GetAccountPositionsResponse,
-    user::{api::non_blocking::User, UserProfileResponse},
-    utils::Sealed,
+    user::{
+        api::non_blocking::User,
+        types::{Account, AccountStatus},
+        UserProfileResponse,
+    },
+    utils::{PageStream, Sealed},
     Error, Result,
 };
 
@@ -53,6 +72,64 @@ impl TradierRestClient {
             .await
             .map_err(Error::NetworkError)
     }
+
+    pub(crate) async fn make_form_service_call(
+        &self,
+        method: reqwest::Method,
+        url: Url,
+        bearer_token: String,
+        form: &[(String, String)],
+    ) -> Result<reqwest::Response> {
+        self.http_client
+            .request(method, url)
+            .bearer_auth(bearer_token)
+            .header("accept", "application/json")
+            .form(form)
+            .send()
+            .await
+            .map_err(Error::NetworkError)
+    }
+
+    /// Fetches balances, positions and orders of a single account concurrently.
+    async fn snapshot_account(
+        &self,
+        account: Account,
+    ) -> std::result::Result<AccountSnapshot, AccountFailure> {
+        let snapshot = async {
+            let account_number: AccountNumber = account.account_number.parse()?;
+            let (balances, positions, orders) = futures_util::try_join!(
+                self.get_account_balances(&account_number),
+                self.get_account_positions(&account_number),
+                self.get_account_orders(&account_number, false),
+            )?;
+            Ok(AccountSnapshot::new(
+                account.account_number.clone(),
+                account.account_type.clone(),
+                balances.balances().clone(),
+                positions.positions().to_vec(),
+                orders
+                    .orders()
+                    .iter()
+                    .filter(|order| !order.status().is_final())
+                    .cloned()
+                    .collect(),
+            ))
+        };
+        snapshot
+            .await
+            .map_err(|error| AccountFailure::new(account.account_number.clone(), error))
+    }
+}
+
+/// Decodes a JSON response body, turning Tradier error bodies on non-success statuses into
+/// [`Error::ApiError`] via [`Error::from_response_body`].
+async fn decode_response<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.map_err(Error::NetworkError)?;
+        return Err(Error::from_response_body(status, &body));
+    }
+    response.json::<T>().await.map_err(Error::NetworkError)
 }
 
 impl Sealed for TradierRestClient {}
@@ -97,4 +174,185 @@ impl Accounts for TradierRestClient {
             .await
             .map_err(Error::NetworkError)
     }
+
+    async fn get_account_orders(
+        &self,
+        account_id: &AccountNumber,
+        include_tags: bool,
+    ) -> Result<GetAccountOrdersResponse> {
+        let mut url = self.get_request_url(&format!("/v1/accounts/{account_id}/orders"))?;
+        url.query_pairs_mut()
+            .append_pair("includeTags", &include_tags.to_string());
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self.make_service_call(url, bearer_auth).await?;
+        decode_response::<GetAccountOrdersResponse>(raw_response).await
+    }
+
+    async fn get_account_order(
+        &self,
+        account_id: &AccountNumber,
+        order_id: u64,
+        include_tags: bool,
+    ) -> Result<GetAccountOrderResponse> {
+        let mut url =
+            self.get_request_url(&format!("/v1/accounts/{account_id}/orders/{order_id}"))?;
+        url.query_pairs_mut()
+            .append_pair("includeTags", &include_tags.to_string());
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self.make_service_call(url, bearer_auth).await?;
+        decode_response::<GetAccountOrderResponse>(raw_response).await
+    }
+
+    async fn get_account_history(
+        &self,
+        account_id: &AccountNumber,
+        query: &AccountHistoryQuery,
+    ) -> Result<GetAccountHistoryResponse> {
+        let mut url = self.get_request_url(&format!("/v1/accounts/{account_id}/history"))?;
+        url.query_pairs_mut().extend_pairs(query.query_pairs());
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self.make_service_call(url, bearer_auth).await?;
+        decode_response::<GetAccountHistoryResponse>(raw_response).await
+    }
+
+    fn stream_account_history(
+        &self,
+        account_id: &AccountNumber,
+        query: AccountHistoryQuery,
+        prefetch: usize,
+    ) -> PageStream<'_, HistoryEvent> {
+        let account_id = account_id.clone();
+        PageStream::new(query.first_page(), prefetch, move |page| {
+            let account_id = account_id.clone();
+            let query = query.with_page(page);
+            async move {
+                self.get_account_history(&account_id, &query)
+                    .await
+                    .map(GetAccountHistoryResponse::into_history)
+            }
+        })
+    }
+
+    async fn get_account_gainloss(
+        &self,
+        account_id: &AccountNumber,
+        query: &GainLossQuery,
+    ) -> Result<GetAccountGainLossResponse> {
+        let mut url = self.get_request_url(&format!("/v1/accounts/{account_id}/gainloss"))?;
+        url.query_pairs_mut().extend_pairs(query.query_pairs());
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self.make_service_call(url, bearer_auth).await?;
+        decode_response::<GetAccountGainLossResponse>(raw_response).await
+    }
+
+    fn stream_account_gainloss(
+        &self,
+        account_id: &AccountNumber,
+        query: GainLossQuery,
+        prefetch: usize,
+    ) -> PageStream<'_, ClosedPosition> {
+        let account_id = account_id.clone();
+        PageStream::new(query.first_page(), prefetch, move |page| {
+            let account_id = account_id.clone();
+            let query = query.with_page(page);
+            async move {
+                self.get_account_gainloss(&account_id, &query)
+                    .await
+                    .map(GetAccountGainLossResponse::into_gainloss)
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Trading for TradierRestClient {
+    async fn place_order<O>(&self, account_id: &AccountNumber, order: &O) -> Result<OrderResponse>
+    where
+        O: OrderRequest + Sync,
+    {
+        let url = self.get_request_url(&format!("/v1/accounts/{account_id}/orders"))?;
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self
+            .make_form_service_call(
+                reqwest::Method::POST,
+                url,
+                bearer_auth,
+                &order.form_params(),
+            )
+            .await?;
+        decode_response::<OrderResponseEnvelope>(raw_response)
+            .await
+            .map(|envelope| envelope.order)
+    }
+
+    async fn preview_order<O>(&self, account_id: &AccountNumber, order: &O) -> Result<OrderPreview>
+    where
+        O: OrderRequest + Sync,
+    {
+        let url = self.get_request_url(&format!("/v1/accounts/{account_id}/orders"))?;
+        let bearer_auth = self.get_bearer_token()?;
+        let mut params = order.form_params();
+        params.push(("preview".to_owned(), "true".to_owned()));
+        let raw_response = self
+            .make_form_service_call(reqwest::Method::POST, url, bearer_auth, &params)
+            .await?;
+        decode_response::<OrderPreviewEnvelope>(raw_response)
+            .await
+            .map(|envelope| envelope.order)
+    }
+
+    async fn modify_order(
+        &self,
+        account_id: &AccountNumber,
+        order_id: u64,
+        modification: &OrderModification,
+    ) -> Result<OrderResponse> {
+        let url = self.get_request_url(&format!("/v1/accounts/{account_id}/orders/{order_id}"))?;
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self
+            .make_form_service_call(
+                reqwest::Method::PUT,
+                url,
+                bearer_auth,
+                &modification.form_params(),
+            )
+            .await?;
+        decode_response::<OrderResponseEnvelope>(raw_response)
+            .await
+            .map(|envelope| envelope.order)
+            .map_err(Error::into_modify_order_error)
+    }
+
+    async fn cancel_order(
+        &self,
+        account_id: &AccountNumber,
+        order_id: u64,
+    ) -> Result<OrderResponse> {
+        let url = self.get_request_url(&format!("/v1/accounts/{account_id}/orders/{order_id}"))?;
+        let bearer_auth = self.get_bearer_token()?;
+        let raw_response = self
+            .make_form_service_call(reqwest::Method::DELETE, url, bearer_auth, &[])
+            .await?;
+        decode_response::<OrderResponseEnvelope>(raw_response)
+            .await
+            .map(|envelope| envelope.order)
+            .map_err(Error::into_cancel_order_error)
+    }
+}
+
+#[async_trait::async_trait]
+impl Portfolio for TradierRestClient {
+    async fn get_portfolio_snapshot(&self) -> Result<PortfolioSnapshot> {
+        let profile = self.get_user_profile().await?;
+        let snapshots = profile
+            .profile
+            .account
+            .into_vec()
+            .into_iter()
+            .filter(|account| account.status == AccountStatus::Active)
+            .map(|account| self.snapshot_account(account));
+        Ok(PortfolioSnapshot::from_results(
+            futures_util::future::join_all(snapshots).await,
+        ))
+    }
 }
@@ -6,6 +6,8 @@ use serde::Deserialize;
 pub enum AccountType {
     Cash,
     Margin,
+    /// Pattern day trader margin account.
+    Pdt,
 }
 
 #[cfg(test)]
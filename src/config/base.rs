@@ -63,6 +63,45 @@ pub struct StreamingConfig {
     pub ws_base_url: String,
     pub events_path: String,
     pub reconnect_interval: u64,
+    #[serde(default)]
+    pub transport: StreamTransport,
+}
+
+/// The transport used to receive market events.
+///
+/// Variants:
+/// - `WebSocket`: Connects to the WebSocket URL returned when the session is created.
+/// - `Http`: POSTs the subscription to [`Config::get_http_url`] and reads the chunked,
+///   newline-delimited response, for hosts that cannot open outbound WebSockets.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamTransport {
+    #[default]
+    WebSocket,
+    Http,
+}
+
+impl FromStr for StreamTransport {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "websocket" | "ws" => Ok(StreamTransport::WebSocket),
+            "http" => Ok(StreamTransport::Http),
+            _ => Err(crate::Error::UnexpectedError(format!(
+                "Unsupported stream transport: {s}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for StreamTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamTransport::WebSocket => f.write_str("websocket"),
+            StreamTransport::Http => f.write_str("http"),
+        }
+    }
 }
 
 /// Implements `fmt::Display` for `Credentials`, providing a JSON-style output
@@ -103,8 +142,12 @@ impl fmt::Display for StreamingConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{\"http_base_url\":\"{}\",\"ws_base_url\":\"{}\",\"events_path\":\"{}\",\"reconnect_interval\":{}}}",
-            self.http_base_url, self.ws_base_url, self.events_path, self.reconnect_interval
+            "{{\"http_base_url\":\"{}\",\"ws_base_url\":\"{}\",\"events_path\":\"{}\",\"reconnect_interval\":{},\"transport\":\"{}\"}}",
+            self.http_base_url,
+            self.ws_base_url,
+            self.events_path,
+            self.reconnect_interval,
+            self.transport
         )
     }
 }
@@ -177,6 +220,10 @@ impl Config {
                     String::from(TRADIER_STREAM_EVENTS_PATH),
                 ),
                 reconnect_interval: get_env_or_default("TRADIER_STREAM_RECONNECT_INTERVAL", 5),
+                transport: get_env_or_default(
+                    "TRADIER_STREAM_TRANSPORT",
+                    StreamTransport::default(),
+                ),
             },
         }
     }
@@ -229,6 +276,7 @@ mod tests_config {
             assert_eq!(config.streaming.ws_base_url, TRADIER_WS_BASE_URL);
             assert_eq!(config.streaming.events_path, TRADIER_STREAM_EVENTS_PATH);
             assert_eq!(config.streaming.reconnect_interval, 5);
+            assert_eq!(config.streaming.transport, StreamTransport::WebSocket);
             assert_eq!(config.rest_api.timeout, 30);
         });
     }
@@ -251,6 +299,7 @@ mod tests_config {
                 ("TRADIER_WS_BASE_URL", "wss://test-ws.tradier.com"),
                 ("TRADIER_STREAM_EVENTS_PATH", "/v1/test/events"),
                 ("TRADIER_STREAM_RECONNECT_INTERVAL", "10"),
+                ("TRADIER_STREAM_TRANSPORT", "http"),
             ],
             || {
                 let config = Config::new();
@@ -273,6 +322,7 @@ mod tests_config {
                 assert_eq!(config.streaming.ws_base_url, "wss://test-ws.tradier.com");
                 assert_eq!(config.streaming.events_path, "/v1/test/events");
                 assert_eq!(config.streaming.reconnect_interval, 10);
+                assert_eq!(config.streaming.transport, StreamTransport::Http);
             },
         );
     }
@@ -1,5 +1,7 @@
 use reqwest::StatusCode;
+use serde::Deserialize;
 
+use crate::utils::OneOrMany;
 use crate::wssession::session::SessionType;
 
 /// A specialized `Result` type for the Tradier API client, using `Error` for errors.
@@ -15,6 +17,12 @@ pub type Result<T> = core::result::Result<T, Error>;
 /// - `CreateSessionError`: Represents a failure in creating a session, providing the `SessionType`,
 ///   HTTP status, and response body for troubleshooting.
 /// - `JsonParsingError`: Raised when parsing JSON data into the expected session response structure fails.
+/// - `StreamEventParseError`: Raised when a streaming payload cannot be parsed into a typed event.
+/// - `InvalidRecording`: Raised when a record of a market session recording cannot be read.
+/// - `InvalidOrder`: Raised when an order request is rejected by local validation.
+/// - `OrderNotModifiable`: Raised when Tradier refuses to modify an order, e.g. because it has filled.
+/// - `OrderNotCancelable`: Raised when Tradier refuses to cancel an order.
+/// - `ApiError`: Raised when Tradier answers with an error status and an error body.
 /// - `MissingAccessToken`: Indicates a missing access token, which is required for API authentication.
 /// - `SessionAlreadyExists`: Raised when attempting to create a duplicate session where one already exists.
 /// - `NetworkError`: Wraps network-related errors that occur during API requests, sourced from `reqwest`.
@@ -24,6 +32,14 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum Error {
     #[error("Unable to parse the string {0} into an AccountId, must be valid ASCII")]
     AccountIdParseError(String),
+
+    /// Error when a string is not a valid OCC option symbol.
+    ///
+    /// # Parameters
+    /// - `String`: The value that failed to parse.
+    #[error("Unable to parse {0} into an OCC option symbol")]
+    OptionSymbolParseError(String),
+
     /// Error when parsing a URL fails.
     ///
     /// # Source
@@ -55,6 +71,45 @@ pub enum Error {
     #[error("Unable to parse Session Response")]
     JsonParsingError(#[from] serde_json::Error),
 
+    /// Error when a payload received on a streaming connection cannot be parsed into a typed event.
+    ///
+    /// # Parameters
+    /// - `String`: The raw payload that failed to parse.
+    /// - `serde_json::Error`: The underlying parsing error.
+    #[error("Unable to parse streaming event {0}: {1}")]
+    StreamEventParseError(String, #[source] serde_json::Error),
+
+    /// Error raised when an order request fails validation before it is sent to Tradier.
+    ///
+    /// # Parameters
+    /// - `String`: Description of the validation failure.
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+
+    /// Error returned by Tradier when an existing order cannot be modified, typically because
+    /// it has already been filled, canceled or expired.
+    ///
+    /// # Parameters
+    /// - `String`: The message returned by Tradier.
+    #[error("Order cannot be modified: {0}")]
+    OrderNotModifiable(String),
+
+    /// Error returned by Tradier when an existing order cannot be canceled.
+    ///
+    /// # Parameters
+    /// - `String`: The message returned by Tradier.
+    #[error("Order cannot be canceled: {0}")]
+    OrderNotCancelable(String),
+
+    /// Error returned by the Tradier API alongside a non-success status.
+    ///
+    /// # Parameters
+    /// - `StatusCode`: HTTP status code returned by the API.
+    /// - `Vec<String>`: The error messages from the response body, or the raw body if it could
+    ///   not be parsed.
+    #[error("Tradier API error. Status: {0}. Errors: {1:?}")]
+    ApiError(StatusCode, Vec<String>),
+
     /// Error when an access token, required for authentication, is missing.
     #[error("Missing Access Token")]
     MissingAccessToken,
@@ -68,6 +123,14 @@ pub enum Error {
     #[error("You are attempting to create a blocking client in an async runtime. Please use the non_blocking client.")]
     BlockingClientInsideAsyncRuntime,
 
+    /// Error when a record of a market session recording cannot be read.
+    ///
+    /// # Parameters
+    /// - `u64`: The position of the record in the recording, starting at zero.
+    /// - `String`: Why the record is invalid.
+    #[error("Invalid recording record {0}: {1}")]
+    InvalidRecording(u64, String),
+
     /// Represents an IO Error
     #[error("IO Error: {0}")]
     IoError(#[from] std::io::Error),
@@ -93,3 +156,137 @@ pub enum Error {
     #[error("Unexpected error: {0}")]
     UnexpectedError(String),
 }
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    errors: ErrorList,
+}
+
+#[derive(Deserialize)]
+struct ErrorList {
+    error: OneOrMany<String>,
+}
+
+/// Messages Tradier answers `modify_order` with when the order is no longer open.
+const ORDER_NOT_MODIFIABLE_MESSAGES: &[&str] = &[
+    "Backend Service failed: order cannot be modified",
+    "Order cannot be modified",
+];
+
+/// Messages Tradier answers `cancel_order` with when the order is no longer open.
+const ORDER_NOT_CANCELABLE_MESSAGES: &[&str] = &[
+    "Backend Service failed: order cannot be canceled",
+    "Order cannot be canceled",
+    "Order is not in a state that can be cancelled",
+];
+
+impl Error {
+    /// Builds an [`Error::ApiError`] from a non-success Tradier response.
+    ///
+    /// Tradier reports failures as `{"errors": {"error": [...]}}`; bodies in any other shape are
+    /// kept whole as the only message.
+    pub(crate) fn from_response_body(status: StatusCode, body: &str) -> Self {
+        let messages = match serde_json::from_str::<ErrorBody>(body) {
+            Ok(parsed) => parsed.errors.error.into_vec(),
+            Err(_) => vec![body.to_owned()],
+        };
+        Error::ApiError(status, messages)
+    }
+
+    /// Turns an [`Error::ApiError`] returned by `modify_order` into
+    /// [`Error::OrderNotModifiable`] if Tradier reported that the order is no longer open.
+    pub(crate) fn into_modify_order_error(self) -> Self {
+        self.map_order_state(ORDER_NOT_MODIFIABLE_MESSAGES, Error::OrderNotModifiable)
+    }
+
+    /// Turns an [`Error::ApiError`] returned by `cancel_order` into
+    /// [`Error::OrderNotCancelable`] if Tradier reported that the order is no longer open.
+    pub(crate) fn into_cancel_order_error(self) -> Self {
+        self.map_order_state(ORDER_NOT_CANCELABLE_MESSAGES, Error::OrderNotCancelable)
+    }
+
+    fn map_order_state(self, known: &[&str], variant: fn(String) -> Self) -> Self {
+        match self {
+            Error::ApiError(status, messages) => {
+                match messages
+                    .iter()
+                    .find(|message| known.contains(&message.as_str()))
+                {
+                    Some(message) => variant(message.clone()),
+                    None => Error::ApiError(status, messages),
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_order_state_errors_are_mapped_per_endpoint() {
+        let error = Error::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":{"error":"Order cannot be modified"}}"#,
+        );
+        assert!(matches!(error, Error::ApiError(StatusCode::BAD_REQUEST, _)));
+        assert!(matches!(
+            error.into_modify_order_error(),
+            Error::OrderNotModifiable(message) if message == "Order cannot be modified"
+        ));
+
+        let error = Error::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":{"error":["Invalid duration","Order is not in a state that can be cancelled"]}}"#,
+        );
+        assert!(matches!(
+            error.into_cancel_order_error(),
+            Error::OrderNotCancelable(_)
+        ));
+    }
+
+    #[test]
+    fn test_only_exact_order_state_messages_are_mapped() {
+        let error = Error::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":{"error":"Order cannot be canceled"}}"#,
+        );
+        assert!(matches!(
+            error.into_modify_order_error(),
+            Error::ApiError(StatusCode::BAD_REQUEST, _)
+        ));
+
+        let error = Error::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":{"error":"Symbol SPY cannot be modified in this account"}}"#,
+        );
+        assert!(matches!(
+            error.into_modify_order_error(),
+            Error::ApiError(StatusCode::BAD_REQUEST, _)
+        ));
+        assert!(matches!(
+            Error::MissingAccessToken.into_cancel_order_error(),
+            Error::MissingAccessToken
+        ));
+    }
+
+    #[test]
+    fn test_from_response_body_falls_back_to_api_error() {
+        let error = Error::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":{"error":["Invalid symbol","Invalid quantity"]}}"#,
+        );
+        assert!(matches!(
+            error,
+            Error::ApiError(StatusCode::BAD_REQUEST, messages) if messages.len() == 2
+        ));
+
+        let error = Error::from_response_body(StatusCode::UNAUTHORIZED, "Invalid Access Token");
+        assert!(matches!(
+            error,
+            Error::ApiError(StatusCode::UNAUTHORIZED, messages) if messages == ["Invalid Access Token"]
+        ));
+    }
+}
@@ -15,20 +15,25 @@ mod accounts;
 mod client;
 pub mod common;
 mod market_data;
-mod streaming;
+mod portfolio;
+pub mod streaming;
 mod trading;
 mod user;
 mod watchlists;
 
 pub mod types {
     pub use crate::accounts::types::*;
+    pub use crate::portfolio::types::*;
+    pub use crate::trading::types::*;
     pub use crate::user::types::*;
-    pub use crate::utils::OneOrMany;
+    pub use crate::utils::{OneOrMany, PageIter, PageStream};
 }
 pub mod blocking {
     pub use super::client::blocking::BlockingTradierRestClient as Client;
     pub mod operation {
         pub use crate::accounts::api::blocking::Accounts;
+        pub use crate::portfolio::api::blocking::Portfolio;
+        pub use crate::trading::api::blocking::Trading;
         pub use crate::user::api::blocking::User;
     }
 }
@@ -37,8 +42,10 @@ pub mod non_blocking {
     pub use super::client::non_blocking::TradierRestClient as Client;
     pub mod operation {
         pub use crate::accounts::api::non_blocking::Accounts;
+        pub use crate::portfolio::api::non_blocking::Portfolio;
+        pub use crate::trading::api::non_blocking::Trading;
         pub use crate::user::api::non_blocking::User;
     }
 }
 
-pub use config::Config;
+pub use config::{Config, StreamTransport};
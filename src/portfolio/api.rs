@@ -0,0 +1,28 @@
+use crate::portfolio::types::PortfolioSnapshot;
+use crate::{error::Result, utils::Sealed};
+
+pub mod non_blocking {
+    use super::*;
+
+    #[async_trait::async_trait]
+    pub trait Portfolio: Sealed {
+        /// Reads the user profile, then concurrently fetches balances, positions and open
+        /// orders for every active account.
+        ///
+        /// Only a failure to read the profile fails the whole call; failures for individual
+        /// accounts are reported in [`PortfolioSnapshot::failures`].
+        async fn get_portfolio_snapshot(&self) -> Result<PortfolioSnapshot>;
+    }
+}
+pub mod blocking {
+    use super::*;
+
+    pub trait Portfolio: Sealed {
+        /// Reads the user profile, then fetches balances, positions and open orders for every
+        /// active account.
+        ///
+        /// Only a failure to read the profile fails the whole call; failures for individual
+        /// accounts are reported in [`PortfolioSnapshot::failures`].
+        fn get_portfolio_snapshot(&self) -> Result<PortfolioSnapshot>;
+    }
+}
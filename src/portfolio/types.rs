@@ -0,0 +1,228 @@
+use crate::accounts::types::{AccountBalances, Order, Position};
+use crate::common::AccountType;
+use crate::Error;
+
+/// Balances, positions and open orders of every active account in the user profile.
+#[derive(Debug)]
+pub struct PortfolioSnapshot {
+    accounts: Vec<AccountSnapshot>,
+    failures: Vec<AccountFailure>,
+    totals: PortfolioTotals,
+}
+
+impl PortfolioSnapshot {
+    /// Builds a snapshot from per-account results, computing the combined totals.
+    pub(crate) fn from_results(
+        results: impl IntoIterator<Item = Result<AccountSnapshot, AccountFailure>>,
+    ) -> Self {
+        let mut accounts = Vec::new();
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(account) => accounts.push(account),
+                Err(failure) => failures.push(failure),
+            }
+        }
+        let totals = accounts
+            .iter()
+            .map(AccountSnapshot::totals)
+            .fold(PortfolioTotals::default(), |sum, account| sum + account);
+        PortfolioSnapshot {
+            accounts,
+            failures,
+            totals,
+        }
+    }
+
+    /// Accounts that were fetched successfully, in profile order.
+    pub fn accounts(&self) -> &[AccountSnapshot] {
+        &self.accounts
+    }
+
+    /// Accounts for which at least one request failed.
+    pub fn failures(&self) -> &[AccountFailure] {
+        &self.failures
+    }
+
+    /// Totals across [`accounts`](PortfolioSnapshot::accounts). Failed accounts are not
+    /// included.
+    pub fn totals(&self) -> PortfolioTotals {
+        self.totals
+    }
+
+    /// Returns `true` if every active account was fetched.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// The state of a single account at the time of the snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountSnapshot {
+    account_number: String,
+    account_type: AccountType,
+    balances: AccountBalances,
+    positions: Vec<Position>,
+    open_orders: Vec<Order>,
+}
+
+impl AccountSnapshot {
+    pub(crate) fn new(
+        account_number: String,
+        account_type: AccountType,
+        balances: AccountBalances,
+        positions: Vec<Position>,
+        open_orders: Vec<Order>,
+    ) -> Self {
+        AccountSnapshot {
+            account_number,
+            account_type,
+            balances,
+            positions,
+            open_orders,
+        }
+    }
+
+    pub fn account_number(&self) -> &str {
+        &self.account_number
+    }
+
+    pub fn account_type(&self) -> &AccountType {
+        &self.account_type
+    }
+
+    pub fn balances(&self) -> &AccountBalances {
+        &self.balances
+    }
+
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Orders that can still fill.
+    pub fn open_orders(&self) -> &[Order] {
+        &self.open_orders
+    }
+
+    /// Totals for this account alone.
+    pub fn totals(&self) -> PortfolioTotals {
+        let summary = self.balances.summary();
+        PortfolioTotals {
+            total_equity: summary.total_equity(),
+            total_cash: summary.total_cash(),
+            market_value: summary.market_value(),
+            open_pl: summary.open_pl(),
+            close_pl: summary.close_pl(),
+            position_count: self.positions.len(),
+            open_order_count: self.open_orders.len(),
+        }
+    }
+}
+
+/// An account that could not be included in a [`PortfolioSnapshot`].
+#[derive(Debug)]
+pub struct AccountFailure {
+    account_number: String,
+    error: Error,
+}
+
+impl AccountFailure {
+    pub(crate) fn new(account_number: String, error: Error) -> Self {
+        AccountFailure {
+            account_number,
+            error,
+        }
+    }
+
+    pub fn account_number(&self) -> &str {
+        &self.account_number
+    }
+
+    /// The first error encountered while fetching the account.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+/// Aggregated values over one or more accounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PortfolioTotals {
+    pub total_equity: f64,
+    pub total_cash: f64,
+    pub market_value: f64,
+    pub open_pl: f64,
+    pub close_pl: f64,
+    pub position_count: usize,
+    pub open_order_count: usize,
+}
+
+impl std::ops::Add for PortfolioTotals {
+    type Output = PortfolioTotals;
+
+    fn add(self, other: PortfolioTotals) -> PortfolioTotals {
+        PortfolioTotals {
+            total_equity: self.total_equity + other.total_equity,
+            total_cash: self.total_cash + other.total_cash,
+            market_value: self.market_value + other.market_value,
+            open_pl: self.open_pl + other.open_pl,
+            close_pl: self.close_pl + other.close_pl,
+            position_count: self.position_count + other.position_count,
+            open_order_count: self.open_order_count + other.open_order_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::accounts::types::GetAccountBalancesResponse;
+
+    fn balances(account_number: &str, total_equity: f64, total_cash: f64) -> AccountBalances {
+        let json = format!(
+            r#"{{"balances":{{"option_short_value":0,"total_equity":{total_equity},"account_number":"{account_number}","account_type":"cash","close_pl":1.5,"current_requirement":0,"equity":0,"long_market_value":0,"market_value":{market_value},"open_pl":-2.0,"option_long_value":0,"option_requirement":0,"pending_orders_count":0,"short_market_value":0,"stock_long_value":0,"total_cash":{total_cash},"uncleared_funds":0,"pending_cash":0,"cash":{{"cash_available":{total_cash},"sweep":0,"unsettled_funds":0}}}}}}"#,
+            market_value = total_equity - total_cash,
+        );
+        serde_json::from_str::<GetAccountBalancesResponse>(&json)
+            .expect("balances fixture to deserialize")
+            .balances()
+            .clone()
+    }
+
+    fn snapshot(account_number: &str, total_equity: f64, total_cash: f64) -> AccountSnapshot {
+        AccountSnapshot {
+            account_number: account_number.to_owned(),
+            account_type: AccountType::Cash,
+            balances: balances(account_number, total_equity, total_cash),
+            positions: vec![],
+            open_orders: vec![],
+        }
+    }
+
+    #[test]
+    fn test_totals_combine_successful_accounts_only() {
+        let snapshot = PortfolioSnapshot::from_results(vec![
+            Ok(snapshot("VA000001", 1000.0, 100.0)),
+            Err(AccountFailure {
+                account_number: "VA000002".to_owned(),
+                error: Error::UnexpectedError("boom".to_owned()),
+            }),
+            Ok(snapshot("VA000003", 500.0, 50.0)),
+        ]);
+
+        assert!(!snapshot.is_complete());
+        assert_eq!(snapshot.accounts.len(), 2);
+        assert_eq!(snapshot.failures[0].account_number, "VA000002");
+        assert_eq!(snapshot.totals.total_equity, 1500.0);
+        assert_eq!(snapshot.totals.total_cash, 150.0);
+        assert_eq!(snapshot.totals.market_value, 1350.0);
+        assert_eq!(snapshot.totals.open_pl, -4.0);
+        assert_eq!(snapshot.totals.close_pl, 3.0);
+    }
+
+    #[test]
+    fn test_empty_snapshot_has_zero_totals() {
+        let snapshot = PortfolioSnapshot::from_results(vec![]);
+        assert!(snapshot.is_complete());
+        assert_eq!(snapshot.totals, PortfolioTotals::default());
+    }
+}
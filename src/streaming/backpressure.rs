@@ -0,0 +1,484 @@
+//! Bounded buffers that decide what happens when a consumer falls behind its stream.
+use std::collections::VecDeque;
+use std::mem::discriminant;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::wssession::{MarketEvent, StreamEvent};
+use crate::Error;
+
+/// What a full buffer does with a new item.
+///
+/// Variants:
+/// - `Block`: Waits until the consumer makes room. Nothing is lost, but a slow consumer slows
+///   down the stream.
+/// - `DropOldest`: Discards the oldest event to make room.
+/// - `ConflateLatest`: Replaces a buffered quote or summary of the same symbol with the new
+///   one, so the consumer only sees the freshest value. Trades and time and sales are never
+///   conflated since each one is a separate print; like events of other symbols, they make
+///   room by discarding the oldest event when the buffer is full.
+///
+/// Lifecycle events and errors are never discarded or conflated; a buffer that is full of them
+/// blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    #[default]
+    Block,
+    DropOldest,
+    ConflateLatest,
+}
+
+/// Items that a bounded buffer may conflate or discard.
+pub trait Conflate {
+    /// Returns `true` if `newer` makes `self` obsolete, so only `newer` needs to be delivered.
+    fn is_superseded_by(&self, newer: &Self) -> bool;
+
+    /// Returns `true` if the item may be discarded when the buffer is full.
+    fn is_droppable(&self) -> bool {
+        true
+    }
+}
+
+impl Conflate for MarketEvent {
+    /// Quotes and summaries describe the current state of a symbol, so a newer one of the same
+    /// type and symbol supersedes them. Trades, extended trades and time and sales never do.
+    fn is_superseded_by(&self, newer: &Self) -> bool {
+        matches!(self, MarketEvent::Quote(_) | MarketEvent::Summary(_))
+            && discriminant(self) == discriminant(newer)
+            && self.symbol() == newer.symbol()
+    }
+}
+
+impl<T: Conflate> Conflate for StreamEvent<T> {
+    fn is_superseded_by(&self, newer: &Self) -> bool {
+        match (self, newer) {
+            (StreamEvent::Event(current), StreamEvent::Event(newer)) => {
+                current.is_superseded_by(newer)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_droppable(&self) -> bool {
+        matches!(self, StreamEvent::Event(event) if event.is_droppable())
+    }
+}
+
+impl<T: Conflate> Conflate for Result<T, Error> {
+    fn is_superseded_by(&self, newer: &Self) -> bool {
+        match (self, newer) {
+            (Ok(current), Ok(newer)) => current.is_superseded_by(newer),
+            _ => false,
+        }
+    }
+
+    fn is_droppable(&self) -> bool {
+        matches!(self, Ok(item) if item.is_droppable())
+    }
+}
+
+/// Number of events a buffer discarded or conflated, shared with the buffer it was taken from.
+#[derive(Debug, Clone, Default)]
+pub struct BackpressureCounters(Arc<Counters>);
+
+#[derive(Debug, Default)]
+struct Counters {
+    dropped: AtomicU64,
+    conflated: AtomicU64,
+}
+
+impl BackpressureCounters {
+    /// Returns the number of events discarded to make room for newer ones.
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of events replaced by a newer event of the same kind and symbol.
+    pub fn conflated(&self) -> u64 {
+        self.0.conflated.load(Ordering::Relaxed)
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    sender_alive: bool,
+    receiver_alive: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+    policy: BackpressurePolicy,
+    counters: BackpressureCounters,
+    item_ready: Notify,
+    space_ready: Notify,
+}
+
+impl<T: Conflate> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Adds `item` to the queue according to the policy, or gives it back if the sender has to
+    /// wait for room.
+    fn push(&self, queue: &mut VecDeque<T>, item: T) -> Result<(), T> {
+        if self.policy == BackpressurePolicy::ConflateLatest && item.is_droppable() {
+            if let Some(current) = queue
+                .iter_mut()
+                .find(|current| current.is_superseded_by(&item))
+            {
+                *current = item;
+                self.counters.0.conflated.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+        }
+        if queue.len() < self.capacity {
+            queue.push_back(item);
+            return Ok(());
+        }
+        if self.policy == BackpressurePolicy::Block || !item.is_droppable() {
+            return Err(item);
+        }
+        match queue.iter().position(Conflate::is_droppable) {
+            Some(oldest) => {
+                queue.remove(oldest);
+                queue.push_back(item);
+                self.counters.0.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+}
+
+/// Creates a bounded buffer of `capacity` items applying `policy` when it is full.
+pub(crate) fn channel<T>(capacity: usize, policy: BackpressurePolicy) -> (Sender<T>, Receiver<T>)
+where
+    T: Conflate + Send + 'static,
+{
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            sender_alive: true,
+            receiver_alive: true,
+        }),
+        capacity: capacity.max(1),
+        policy,
+        counters: BackpressureCounters::default(),
+        item_ready: Notify::new(),
+        space_ready: Notify::new(),
+    });
+    let items = stream::unfold(shared.clone(), |shared| async move {
+        loop {
+            {
+                let mut state = shared.lock();
+                if let Some(item) = state.queue.pop_front() {
+                    drop(state);
+                    shared.space_ready.notify_one();
+                    return Some((item, shared));
+                }
+                if !state.sender_alive {
+                    return None;
+                }
+            }
+            shared.item_ready.notified().await;
+        }
+    })
+    .boxed();
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared, items },
+    )
+}
+
+/// The producing half of a bounded buffer.
+pub(crate) struct Sender<T: Conflate> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Conflate> Sender<T> {
+    /// Adds `item` to the buffer, waiting for room only when the policy requires it. Gives the
+    /// item back if the receiver was dropped.
+    pub(crate) async fn send(&self, mut item: T) -> Result<(), T> {
+        loop {
+            {
+                let mut state = self.shared.lock();
+                if !state.receiver_alive {
+                    return Err(item);
+                }
+                match self.shared.push(&mut state.queue, item) {
+                    Ok(()) => {
+                        drop(state);
+                        self.shared.item_ready.notify_one();
+                        return Ok(());
+                    }
+                    Err(rejected) => item = rejected,
+                }
+            }
+            self.shared.space_ready.notified().await;
+        }
+    }
+}
+
+impl<T: Conflate> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.lock().sender_alive = false;
+        self.shared.item_ready.notify_one();
+    }
+}
+
+/// The consuming half of a bounded buffer.
+pub(crate) struct Receiver<T: Conflate> {
+    shared: Arc<Shared<T>>,
+    items: BoxStream<'static, T>,
+}
+
+impl<T: Conflate> Receiver<T> {
+    /// Receives the next item, or `None` once the sender is gone and the buffer is empty.
+    pub(crate) async fn recv(&mut self) -> Option<T> {
+        self.items.next().await
+    }
+
+    pub(crate) fn counters(&self) -> BackpressureCounters {
+        self.shared.counters.clone()
+    }
+}
+
+impl<T: Conflate> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.items.poll_next_unpin(cx)
+    }
+}
+
+impl<T: Conflate> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receiver_alive = false;
+        self.shared.space_ready.notify_one();
+    }
+}
+
+/// A stream read ahead into a bounded buffer by a background task, so that the connection is
+/// drained even when the consumer is slow.
+///
+/// What happens once the buffer is full is decided by the [`BackpressurePolicy`]; the
+/// [`counters`](BufferedStream::counters) report how many events were lost to it. Dropping the
+/// stream stops the background task and drops the source.
+pub struct BufferedStream<T: Conflate> {
+    items: Receiver<T>,
+    task: JoinHandle<()>,
+}
+
+impl<T> BufferedStream<T>
+where
+    T: Conflate + Send + 'static,
+{
+    /// Spawns the task that reads `source` into a buffer of `capacity` items. Must be called
+    /// from within a Tokio runtime.
+    pub fn new<S>(source: S, capacity: usize, policy: BackpressurePolicy) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        let (sender, items) = channel(capacity, policy);
+        let task = tokio::spawn(async move {
+            let mut source = std::pin::pin!(source);
+            while let Some(item) = source.next().await {
+                if sender.send(item).await.is_err() {
+                    break;
+                }
+            }
+        });
+        BufferedStream { items, task }
+    }
+
+    /// Returns the counters of discarded and conflated events.
+    pub fn counters(&self) -> BackpressureCounters {
+        self.items.counters()
+    }
+}
+
+impl<T: Conflate> Stream for BufferedStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.items.poll_next_unpin(cx)
+    }
+}
+
+impl<T: Conflate> Drop for BufferedStream<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str, bid: f64) -> MarketEvent {
+        serde_json::from_str(&format!(
+            r#"{{"type":"quote","symbol":"{symbol}","bid":{bid},"bidsz":1,"bidexch":"Q","biddate":"0","ask":{bid},"asksz":1,"askexch":"Q","askdate":"0"}}"#
+        ))
+        .unwrap()
+    }
+
+    fn summary(symbol: &str) -> MarketEvent {
+        serde_json::from_str(&format!(r#"{{"type":"summary","symbol":"{symbol}"}}"#)).unwrap()
+    }
+
+    fn trade(symbol: &str, price: f64) -> MarketEvent {
+        serde_json::from_str(&format!(
+            r#"{{"type":"trade","symbol":"{symbol}","exch":"Q","price":"{price}","size":"100","cvol":"100","date":"0","last":"{price}"}}"#
+        ))
+        .unwrap()
+    }
+
+    fn bid(event: &MarketEvent) -> f64 {
+        match event {
+            MarketEvent::Quote(quote) => quote.bid,
+            other => panic!("expected a quote, got {other:?}"),
+        }
+    }
+
+    async fn drain<T: Conflate>(mut receiver: Receiver<T>) -> Vec<T> {
+        let mut items = vec![];
+        while let Some(item) = receiver.recv().await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_the_newest_events() {
+        let (sender, receiver) = channel(2, BackpressurePolicy::DropOldest);
+        for bid in [1.0, 2.0, 3.0, 4.0] {
+            sender.send(quote("SPY", bid)).await.unwrap();
+        }
+        drop(sender);
+        let counters = receiver.counters();
+
+        let bids: Vec<f64> = drain(receiver).await.iter().map(bid).collect();
+        assert_eq!(bids, vec![3.0, 4.0]);
+        assert_eq!(counters.dropped(), 2);
+        assert_eq!(counters.conflated(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_conflate_latest_keeps_one_event_per_kind_and_symbol() {
+        let (sender, receiver) = channel(8, BackpressurePolicy::ConflateLatest);
+        for event in [
+            quote("SPY", 1.0),
+            quote("C", 10.0),
+            summary("SPY"),
+            quote("SPY", 2.0),
+            quote("C", 11.0),
+            quote("SPY", 3.0),
+        ] {
+            sender.send(event).await.unwrap();
+        }
+        drop(sender);
+        let counters = receiver.counters();
+
+        let events = drain(receiver).await;
+        assert_eq!(events.len(), 3);
+        assert_eq!((events[0].symbol(), bid(&events[0])), ("SPY", 3.0));
+        assert_eq!((events[1].symbol(), bid(&events[1])), ("C", 11.0));
+        assert!(matches!(events[2], MarketEvent::Summary(_)));
+        assert_eq!(counters.conflated(), 3);
+        assert_eq!(counters.dropped(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_conflate_latest_drops_the_oldest_trades() {
+        let (sender, receiver) = channel(2, BackpressurePolicy::ConflateLatest);
+        for price in [1.0, 2.0, 3.0] {
+            sender.send(trade("SPY", price)).await.unwrap();
+        }
+        drop(sender);
+        let counters = receiver.counters();
+
+        let prices: Vec<f64> = drain(receiver)
+            .await
+            .iter()
+            .map(|event| match event {
+                MarketEvent::Trade(trade) => trade.price,
+                other => panic!("expected a trade, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(prices, vec![2.0, 3.0]);
+        assert_eq!(counters.conflated(), 0);
+        assert_eq!(counters.dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_the_consumer() {
+        let (sender, mut receiver) = channel(1, BackpressurePolicy::Block);
+        sender.send(quote("SPY", 1.0)).await.unwrap();
+
+        let producer = tokio::spawn(async move {
+            sender.send(quote("SPY", 2.0)).await.unwrap();
+        });
+        tokio::task::yield_now().await;
+        assert!(!producer.is_finished());
+
+        assert_eq!(bid(&receiver.recv().await.unwrap()), 1.0);
+        producer.await.unwrap();
+        assert_eq!(bid(&receiver.recv().await.unwrap()), 2.0);
+        assert!(receiver.recv().await.is_none());
+        assert_eq!(receiver.counters().dropped(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_are_never_dropped() {
+        let (sender, receiver) = channel(2, BackpressurePolicy::DropOldest);
+        sender
+            .send(Ok(StreamEvent::Disconnected(None)))
+            .await
+            .unwrap();
+        for bid in [1.0, 2.0, 3.0] {
+            sender
+                .send(Ok(StreamEvent::Event(quote("SPY", bid))))
+                .await
+                .unwrap();
+        }
+        drop(sender);
+
+        let items = drain(receiver).await;
+        assert!(matches!(items[0], Ok(StreamEvent::Disconnected(None))));
+        assert!(matches!(&items[1], Ok(StreamEvent::Event(event)) if bid(event) == 3.0));
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_once_the_receiver_is_dropped() {
+        let (sender, receiver) = channel::<MarketEvent>(1, BackpressurePolicy::Block);
+        drop(receiver);
+        assert!(sender.send(summary("SPY")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_buffered_stream_reads_ahead() {
+        let source = stream::iter((1..=5).map(|bid| quote("SPY", bid as f64)));
+        let mut buffered = BufferedStream::new(source, 2, BackpressurePolicy::ConflateLatest);
+        let counters = buffered.counters();
+        tokio::task::yield_now().await;
+
+        let mut bids = vec![];
+        while let Some(event) = buffered.next().await {
+            bids.push(bid(&event));
+        }
+        assert_eq!(bids.last(), Some(&5.0));
+        assert_eq!(bids.len() as u64 + counters.conflated(), 5);
+    }
+}
@@ -0,0 +1,410 @@
+//! Aggregation of trade ticks into OHLCV bars.
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, TimeZone, Utc, Weekday};
+
+use crate::wssession::MarketEvent;
+
+/// Open, high, low, close and volume of a symbol over one interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    pub symbol: String,
+    /// Start of the interval, inclusive.
+    pub start: DateTime<Utc>,
+    /// End of the interval, exclusive.
+    pub end: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: u64,
+    /// Number of ticks aggregated into the bar.
+    pub trades: u64,
+    /// `false` for partial bars whose interval may still receive ticks.
+    pub complete: bool,
+}
+
+/// A bar still accepting ticks, with the times of its first and last tick so that ticks
+/// arriving out of order do not change its open or close.
+#[derive(Debug, Clone)]
+struct OpenBar {
+    bar: Bar,
+    first: DateTime<Utc>,
+    last: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct SymbolBars {
+    open: BTreeMap<DateTime<Utc>, OpenBar>,
+    /// End of the last emitted bar; ticks before it are late.
+    emitted_until: Option<DateTime<Utc>>,
+    /// Time of the latest tick received.
+    watermark: Option<DateTime<Utc>>,
+}
+
+/// Builds OHLCV bars of a fixed interval from trade, extended trade and time and sale events.
+///
+/// Intervals are aligned to exchange time (US Eastern, daylight saving time included) from
+/// midnight, so hourly bars start on the hour and 4-hour bars start at 00:00, 04:00, 08:00 and
+/// so on in New York; the last bar of a day ends at midnight when the interval does not divide
+/// a day. Subscribe to either trades or time and sales, as events of both kinds are counted.
+/// Canceled and corrected time and sales are ignored.
+///
+/// A bar is complete once a tick of the same symbol is received `grace` after its end, or when
+/// [`close_due`](BarAggregator::close_due) is called with a later time. Ticks that arrive
+/// within the grace period still update their bar; ticks for a bar that was already emitted
+/// are discarded and counted by [`late_ticks`](BarAggregator::late_ticks).
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use tradier::streaming::BarAggregator;
+///
+/// let mut five_minutes = BarAggregator::builder()
+///     .interval(Duration::from_secs(300))
+///     .grace(Duration::from_secs(2))
+///     .build();
+/// assert!(five_minutes.partials().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct BarAggregator {
+    interval: TimeDelta,
+    grace: TimeDelta,
+    symbols: HashMap<String, SymbolBars>,
+    late_ticks: u64,
+}
+
+#[bon::bon]
+impl BarAggregator {
+    /// Constructs a new `BarAggregator`.
+    ///
+    /// # Arguments
+    /// - `interval`: The length of the bars, at least one second.
+    /// - `grace`: How long after its end a bar still accepts late ticks. Defaults to zero.
+    #[builder(builder_type(vis = "pub"))]
+    pub fn new(interval: Duration, #[builder(default)] grace: Duration) -> Self {
+        let interval = interval.max(Duration::from_secs(1));
+        BarAggregator {
+            interval: TimeDelta::from_std(interval).unwrap_or(TimeDelta::days(1)),
+            grace: TimeDelta::from_std(grace).unwrap_or(TimeDelta::MAX),
+            symbols: HashMap::new(),
+            late_ticks: 0,
+        }
+    }
+
+    /// Adds the tick of `event`, if it carries one, and returns the bars of its symbol that
+    /// this tick completed, oldest first.
+    pub fn push(&mut self, event: &MarketEvent) -> Vec<Bar> {
+        let Some((price, size, time)) = tick(event) else {
+            return vec![];
+        };
+        let (start, end) = self.bounds(time);
+        let symbol = event.symbol();
+        let bars = self.symbols.entry(symbol.to_owned()).or_default();
+        if bars.emitted_until.is_some_and(|emitted| end <= emitted) {
+            self.late_ticks += 1;
+            return vec![];
+        }
+
+        let open = bars.open.entry(start).or_insert_with(|| OpenBar {
+            bar: Bar {
+                symbol: symbol.to_owned(),
+                start,
+                end,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: 0,
+                trades: 0,
+                complete: false,
+            },
+            first: time,
+            last: time,
+        });
+        open.bar.high = open.bar.high.max(price);
+        open.bar.low = open.bar.low.min(price);
+        open.bar.volume += size;
+        open.bar.trades += 1;
+        if time < open.first {
+            open.first = time;
+            open.bar.open = price;
+        }
+        if time >= open.last {
+            open.last = time;
+            open.bar.close = price;
+        }
+
+        let watermark = bars.watermark.map_or(time, |watermark| watermark.max(time));
+        bars.watermark = Some(watermark);
+        complete(bars, before_grace(watermark, self.grace))
+    }
+
+    /// Completes the bars of every symbol whose grace period ended before `now`, for symbols
+    /// that stopped trading. Returns them ordered by start, then symbol.
+    pub fn close_due(&mut self, now: DateTime<Utc>) -> Vec<Bar> {
+        self.complete_all(before_grace(now, self.grace))
+    }
+
+    /// Completes every open bar, for example when the stream ended.
+    pub fn flush(&mut self) -> Vec<Bar> {
+        self.complete_all(DateTime::<Utc>::MAX_UTC)
+    }
+
+    /// Returns the latest bar of `symbol` that is still open.
+    pub fn partial(&self, symbol: &str) -> Option<Bar> {
+        let bars = self.symbols.get(symbol)?;
+        bars.open.values().next_back().map(|open| open.bar.clone())
+    }
+
+    /// Returns every bar that is still open, ordered by start, then symbol.
+    pub fn partials(&self) -> Vec<Bar> {
+        let mut partials: Vec<Bar> = self
+            .symbols
+            .values()
+            .flat_map(|bars| bars.open.values().map(|open| open.bar.clone()))
+            .collect();
+        sort(&mut partials);
+        partials
+    }
+
+    /// Returns the number of ticks discarded because their bar was already emitted.
+    pub fn late_ticks(&self) -> u64 {
+        self.late_ticks
+    }
+
+    fn complete_all(&mut self, before: DateTime<Utc>) -> Vec<Bar> {
+        let mut completed: Vec<Bar> = self
+            .symbols
+            .values_mut()
+            .flat_map(|bars| complete(bars, before))
+            .collect();
+        sort(&mut completed);
+        completed
+    }
+
+    /// Returns the start and end of the interval containing `time`.
+    fn bounds(&self, time: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let offset = exchange_offset(time);
+        let local = time + offset;
+        let midnight = Utc.from_utc_datetime(&local.date_naive().and_time(Default::default()));
+        let elapsed = (local - midnight).num_milliseconds();
+        let interval = self.interval.num_milliseconds();
+        let start = midnight + TimeDelta::milliseconds(elapsed - elapsed % interval);
+        let end = (start + self.interval).min(midnight + TimeDelta::days(1));
+        (start - offset, end - offset)
+    }
+}
+
+/// Removes and returns the open bars that ended at or before `before`.
+fn complete(bars: &mut SymbolBars, before: DateTime<Utc>) -> Vec<Bar> {
+    let mut completed = vec![];
+    while let Some(entry) = bars.open.first_entry() {
+        if entry.get().bar.end > before {
+            break;
+        }
+        let mut bar = entry.remove().bar;
+        bar.complete = true;
+        bars.emitted_until = Some(bar.end);
+        completed.push(bar);
+    }
+    completed
+}
+
+/// Returns the latest end a bar can have to be complete at `time`.
+fn before_grace(time: DateTime<Utc>, grace: TimeDelta) -> DateTime<Utc> {
+    time.checked_sub_signed(grace)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+fn sort(bars: &mut [Bar]) {
+    bars.sort_by(|a, b| (a.start, &a.symbol).cmp(&(b.start, &b.symbol)));
+}
+
+/// Returns the price, size and time of the trade carried by `event`.
+fn tick(event: &MarketEvent) -> Option<(f64, u64, DateTime<Utc>)> {
+    match event {
+        MarketEvent::Trade(trade) | MarketEvent::Tradex(trade) => {
+            Some((trade.price, trade.size, trade.date))
+        }
+        MarketEvent::Timesale(sale) if !sale.cancel && !sale.correction => {
+            Some((sale.last, sale.size, sale.date))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the offset of New York time from UTC at `time`: daylight saving time runs from
+/// 2:00 on the second Sunday of March to 2:00 on the first Sunday of November.
+fn exchange_offset(time: DateTime<Utc>) -> TimeDelta {
+    let year = time.year();
+    let dst_start = nth_sunday(year, 3, 2)
+        .and_hms_opt(7, 0, 0)
+        .map(|t| t.and_utc());
+    let dst_end = nth_sunday(year, 11, 1)
+        .and_hms_opt(6, 0, 0)
+        .map(|t| t.and_utc());
+    match (dst_start, dst_end) {
+        (Some(start), Some(end)) if start <= time && time < end => TimeDelta::hours(-4),
+        _ => TimeDelta::hours(-5),
+    }
+}
+
+fn nth_sunday(year: i32, month: u32, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(time: &str) -> i64 {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().to_utc()
+    }
+
+    fn trade(symbol: &str, price: f64, size: u64, time: &str) -> MarketEvent {
+        serde_json::from_str(&format!(
+            r#"{{"type":"trade","symbol":"{symbol}","exch":"Q","price":"{price}","size":"{size}","date":"{}"}}"#,
+            millis(time)
+        ))
+        .unwrap()
+    }
+
+    fn minutes(minutes: u64) -> BarAggregator {
+        BarAggregator::builder()
+            .interval(Duration::from_secs(minutes * 60))
+            .build()
+    }
+
+    #[test]
+    fn test_aggregates_ticks_into_bars() {
+        let mut bars = minutes(1);
+        assert!(bars
+            .push(&trade("SPY", 100.0, 10, "2024-01-10T14:30:05Z"))
+            .is_empty());
+        assert!(bars
+            .push(&trade("SPY", 101.5, 20, "2024-01-10T14:30:20Z"))
+            .is_empty());
+        assert!(bars
+            .push(&trade("SPY", 99.5, 5, "2024-01-10T14:30:40Z"))
+            .is_empty());
+        assert!(bars
+            .push(&trade("SPY", 100.5, 1, "2024-01-10T14:30:59Z"))
+            .is_empty());
+
+        let completed = bars.push(&trade("SPY", 102.0, 7, "2024-01-10T14:31:00Z"));
+        assert_eq!(
+            completed,
+            vec![Bar {
+                symbol: "SPY".to_owned(),
+                start: utc("2024-01-10T14:30:00Z"),
+                end: utc("2024-01-10T14:31:00Z"),
+                open: 100.0,
+                high: 101.5,
+                low: 99.5,
+                close: 100.5,
+                volume: 36,
+                trades: 4,
+                complete: true,
+            }]
+        );
+        let partial = bars.partial("SPY").unwrap();
+        assert_eq!(
+            (partial.open, partial.volume, partial.complete),
+            (102.0, 7, false)
+        );
+    }
+
+    #[test]
+    fn test_intervals_are_aligned_to_exchange_time() {
+        let four_hours = minutes(240);
+        // 10:00 in New York, during standard time.
+        assert_eq!(
+            four_hours.bounds(utc("2024-01-10T15:00:00Z")),
+            (utc("2024-01-10T13:00:00Z"), utc("2024-01-10T17:00:00Z"))
+        );
+        // 11:00 in New York, during daylight saving time.
+        assert_eq!(
+            four_hours.bounds(utc("2024-07-10T15:00:00Z")),
+            (utc("2024-07-10T12:00:00Z"), utc("2024-07-10T16:00:00Z"))
+        );
+
+        let fifteen = minutes(15);
+        assert_eq!(
+            fifteen.bounds(utc("2024-07-10T13:37:10Z")),
+            (utc("2024-07-10T13:30:00Z"), utc("2024-07-10T13:45:00Z"))
+        );
+
+        // Seven-hour bars end at midnight in New York.
+        assert_eq!(
+            minutes(420).bounds(utc("2024-01-11T03:00:00Z")),
+            (utc("2024-01-11T02:00:00Z"), utc("2024-01-11T05:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_late_ticks_within_grace_update_their_bar() {
+        let mut bars = BarAggregator::builder()
+            .interval(Duration::from_secs(60))
+            .grace(Duration::from_secs(5))
+            .build();
+        bars.push(&trade("SPY", 100.0, 1, "2024-01-10T14:30:10Z"));
+        assert!(bars
+            .push(&trade("SPY", 101.0, 1, "2024-01-10T14:31:02Z"))
+            .is_empty());
+
+        // Older than the first tick of its bar, so it becomes the open.
+        bars.push(&trade("SPY", 98.0, 1, "2024-01-10T14:30:01Z"));
+        let completed = bars.push(&trade("SPY", 101.5, 1, "2024-01-10T14:31:06Z"));
+        assert_eq!(completed.len(), 1);
+        assert_eq!((completed[0].open, completed[0].low), (98.0, 98.0));
+        assert_eq!((completed[0].close, completed[0].trades), (100.0, 2));
+
+        assert!(bars
+            .push(&trade("SPY", 50.0, 1, "2024-01-10T14:30:30Z"))
+            .is_empty());
+        assert_eq!(bars.late_ticks(), 1);
+        assert_eq!(bars.partial("SPY").unwrap().low, 101.0);
+    }
+
+    #[test]
+    fn test_close_due_and_flush_complete_quiet_symbols() {
+        let mut bars = minutes(5);
+        bars.push(&trade("SPY", 100.0, 1, "2024-01-10T14:31:00Z"));
+        bars.push(&trade("C", 50.0, 1, "2024-01-10T14:29:00Z"));
+        assert_eq!(bars.partials().len(), 2);
+
+        let due = bars.close_due(utc("2024-01-10T14:32:00Z"));
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].symbol.as_str(), due[0].close), ("C", 50.0));
+
+        let flushed = bars.flush();
+        let symbols: Vec<&str> = flushed.iter().map(|bar| bar.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["SPY"]);
+        assert!(flushed.iter().all(|bar| bar.complete));
+        assert!(bars.partials().is_empty());
+    }
+
+    #[test]
+    fn test_canceled_timesales_are_ignored() {
+        let mut bars = minutes(1);
+        let sale = |cancel: bool| -> MarketEvent {
+            serde_json::from_str(&format!(
+                r#"{{"type":"timesale","symbol":"SPY","exch":"Q","bid":"1","ask":"2","last":"1.5","size":"100","date":"{}","seq":1,"flag":"","cancel":{cancel},"correction":false,"session":"normal"}}"#,
+                millis("2024-01-10T14:30:00Z")
+            ))
+            .unwrap()
+        };
+        bars.push(&sale(true));
+        assert!(bars.partial("SPY").is_none());
+        bars.push(&sale(false));
+        assert_eq!(bars.partial("SPY").unwrap().volume, 100);
+    }
+}
@@ -1 +1,21 @@
+//! # Stream Processing
+//!
+//! Building blocks that sit on top of the market streams of [`crate::wssession`]:
+//!
+//! - **`MarketMultiplexer`**: Shares the single market session Tradier allows between many
+//!   in-process subscribers, each receiving only the symbols it registered.
+//! - **`BufferedStream`**: Reads a stream ahead into a bounded buffer whose
+//!   `BackpressurePolicy` blocks, drops the oldest events or conflates them per symbol when a
+//!   consumer falls behind.
+//! - **`QuoteBook`**: Caches the latest quote, trade and daily summary of every symbol, with
+//!   snapshot reads and a change notification per symbol.
+//! - **`BarAggregator`**: Builds OHLCV bars aligned to exchange time from trade ticks.
+mod backpressure;
+mod bars;
+mod multiplexer;
+mod quote_book;
 
+pub use backpressure::{BackpressureCounters, BackpressurePolicy, BufferedStream, Conflate};
+pub use bars::{Bar, BarAggregator};
+pub use multiplexer::{MarketMultiplexer, MarketSubscriber};
+pub use quote_book::{QuoteBook, QuoteSnapshot};
@@ -0,0 +1,673 @@
+//! Fan-out of a single market session to many in-process subscribers.
+use std::collections::{BTreeSet, HashMap};
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures_util::{Stream, StreamExt};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::backpressure::{self, BackpressureCounters, BackpressurePolicy, Receiver, Sender};
+use crate::wssession::{
+    MarketEvent, MarketSession, MarketSessionFilter, MarketSessionPayload, ReconnectPolicy,
+    StreamEvent, SubscriptionHandle,
+};
+use crate::{Config, Error, Result};
+
+/// Shares one [`MarketSession`] between many subscribers.
+///
+/// Tradier allows a single market session per user, so services in the same process that each
+/// need market data register with one multiplexer instead of creating their own sessions.
+/// Every [`MarketSubscriber`] declares the symbols it is interested in; the multiplexer
+/// subscribes to the union of those symbols and delivers each event only to the subscribers
+/// interested in its symbol, through a bounded buffer per subscriber whose
+/// [`BackpressurePolicy`] decides what happens when that subscriber falls behind.
+///
+/// The stream is opened once the first symbol is registered. Opening it and reconnecting are
+/// both retried according to the configured [`ReconnectPolicy`], and every subscriber receives
+/// the resulting [`StreamEvent`]s so it can tell where events may have been missed. Dropping
+/// the multiplexer, or the policy giving up, closes the stream and releases the session;
+/// subscribers then receive `None`.
+///
+/// # Example
+///
+/// ```no_run
+/// use tradier::Config;
+/// use tradier::streaming::MarketMultiplexer;
+/// use tradier::wssession::MarketSession;
+/// #[tokio::main]
+/// async fn main() {
+///     let config = Config::new();
+///     let session = MarketSession::new(&config).await.unwrap();
+///     let multiplexer = MarketMultiplexer::builder()
+///         .session(session)
+///         .config(&config)
+///         .build();
+///
+///     let mut spy = multiplexer.subscribe(["SPY"]);
+///     let mut tech = multiplexer.subscribe(["AAPL", "MSFT"]);
+///     while let Some(event) = spy.recv().await {
+///         println!("{:?}", event);
+///     }
+/// }
+/// ```
+pub struct MarketMultiplexer {
+    shared: Arc<Shared>,
+    task: JoinHandle<()>,
+}
+
+#[bon::bon]
+impl MarketMultiplexer {
+    /// Constructs a new `MarketMultiplexer` and spawns the task that routes events. Must be
+    /// called from within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// - `session`: The market session to share.
+    /// - `config`: The configuration used to renew the session.
+    /// - `filters`: The event types to subscribe to, all of them by default.
+    /// - `capacity`: The number of events buffered per subscriber. Defaults to 1024.
+    /// - `backpressure`: What a full subscriber buffer does with new events. Defaults to
+    ///   [`BackpressurePolicy::Block`], which slows down every subscriber to the slowest one.
+    /// - `policy`: The backoff between reconnection attempts. Defaults to
+    ///   [`ReconnectPolicy::from_config`].
+    #[builder(builder_type(vis = "pub"))]
+    pub fn new(
+        session: MarketSession<'static>,
+        config: &Config,
+        filters: Option<&[MarketSessionFilter]>,
+        #[builder(default = 1024)] capacity: usize,
+        #[builder(default)] backpressure: BackpressurePolicy,
+        policy: Option<ReconnectPolicy>,
+    ) -> Self {
+        let (union, union_updates) = watch::channel(Vec::new());
+        let shared = Arc::new(Shared {
+            subscribers: Mutex::new(Subscribers::default()),
+            next_id: AtomicU64::new(0),
+            capacity: capacity.max(1),
+            backpressure,
+            union,
+        });
+        let task = tokio::spawn(run(
+            session,
+            config.clone(),
+            filters.map(<[_]>::to_vec),
+            policy.unwrap_or_else(|| ReconnectPolicy::from_config(config)),
+            shared.clone(),
+            union_updates,
+        ));
+        MarketMultiplexer { shared, task }
+    }
+
+    /// Registers a subscriber interested in `symbols`.
+    pub fn subscribe<I, S>(&self, symbols: I) -> MarketSubscriber
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Shared::register(&self.shared, symbols.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the symbols the session is subscribed to: the union of the interests of all
+    /// subscribers.
+    pub fn symbols(&self) -> Vec<String> {
+        self.shared.union.borrow().clone()
+    }
+}
+
+impl Drop for MarketMultiplexer {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.shared.close();
+    }
+}
+
+/// One consumer of a [`MarketMultiplexer`], receiving the events of the symbols it registered.
+///
+/// Dropping the subscriber unregisters it; symbols no other subscriber is interested in are
+/// then removed from the session.
+pub struct MarketSubscriber {
+    id: u64,
+    shared: Arc<Shared>,
+    events: Receiver<StreamEvent<MarketEvent>>,
+}
+
+impl MarketSubscriber {
+    /// Receives the next event or change in the state of the shared connection, or `None` once
+    /// the multiplexer stopped.
+    ///
+    /// The error of a [`Disconnected`](StreamEvent::Disconnected) event is delivered to every
+    /// subscriber as an [`Error::UnexpectedError`] holding its message, since errors cannot be
+    /// cloned.
+    pub async fn recv(&mut self) -> Option<StreamEvent<MarketEvent>> {
+        self.events.recv().await
+    }
+
+    /// Returns the counters of events this subscriber lost to the backpressure policy.
+    pub fn counters(&self) -> BackpressureCounters {
+        self.events.counters()
+    }
+
+    /// Returns the symbols this subscriber is interested in.
+    pub fn symbols(&self) -> Vec<String> {
+        self.shared.update(self.id, |_| ()).unwrap_or_default()
+    }
+
+    /// Adds symbols to the interests of this subscriber.
+    pub fn add_symbols<I, S>(&self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.shared.update(self.id, |interest| {
+            interest.extend(symbols.into_iter().map(Into::into))
+        });
+    }
+
+    /// Removes symbols from the interests of this subscriber.
+    pub fn remove_symbols<I, S>(&self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.shared.update(self.id, |interest| {
+            for symbol in symbols {
+                interest.remove(symbol.as_ref());
+            }
+        });
+    }
+}
+
+impl Stream for MarketSubscriber {
+    type Item = StreamEvent<MarketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for MarketSubscriber {
+    fn drop(&mut self) {
+        self.shared.unregister(self.id);
+    }
+}
+
+struct Subscriber {
+    symbols: BTreeSet<String>,
+    sender: Arc<Sender<StreamEvent<MarketEvent>>>,
+}
+
+#[derive(Default)]
+struct Subscribers {
+    by_id: HashMap<u64, Subscriber>,
+    closed: bool,
+}
+
+/// State shared by the multiplexer, its subscribers and the routing task.
+struct Shared {
+    subscribers: Mutex<Subscribers>,
+    next_id: AtomicU64,
+    capacity: usize,
+    backpressure: BackpressurePolicy,
+    union: watch::Sender<Vec<String>>,
+}
+
+impl Shared {
+    fn register(shared: &Arc<Shared>, symbols: BTreeSet<String>) -> MarketSubscriber {
+        let id = shared.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, events) = backpressure::channel(shared.capacity, shared.backpressure);
+        let mut subscribers = shared.lock();
+        if !subscribers.closed {
+            let sender = Arc::new(sender);
+            subscribers.by_id.insert(id, Subscriber { symbols, sender });
+            shared.publish_union(&subscribers);
+        }
+        MarketSubscriber {
+            id,
+            shared: shared.clone(),
+            events,
+        }
+    }
+
+    /// Applies `change` to the interests of subscriber `id` and returns the resulting symbols,
+    /// or `None` if the subscriber is no longer registered.
+    fn update(&self, id: u64, change: impl FnOnce(&mut BTreeSet<String>)) -> Option<Vec<String>> {
+        let mut subscribers = self.lock();
+        let subscriber = subscribers.by_id.get_mut(&id)?;
+        change(&mut subscriber.symbols);
+        let symbols = subscriber.symbols.iter().cloned().collect();
+        self.publish_union(&subscribers);
+        Some(symbols)
+    }
+
+    fn unregister(&self, id: u64) {
+        let mut subscribers = self.lock();
+        if subscribers.by_id.remove(&id).is_some() {
+            self.publish_union(&subscribers);
+        }
+    }
+
+    /// Drops every sender, so that subscribers see the end of their stream.
+    fn close(&self) {
+        let mut subscribers = self.lock();
+        subscribers.closed = true;
+        subscribers.by_id.clear();
+    }
+
+    fn publish_union(&self, subscribers: &Subscribers) {
+        let union: BTreeSet<&String> = subscribers
+            .by_id
+            .values()
+            .flat_map(|subscriber| &subscriber.symbols)
+            .collect();
+        let union: Vec<String> = union.into_iter().cloned().collect();
+        self.union.send_if_modified(|current| {
+            if *current == union {
+                return false;
+            }
+            *current = union;
+            true
+        });
+    }
+
+    /// Sends `event` to every subscriber interested in its symbol, applying the backpressure
+    /// policy to their buffers.
+    async fn dispatch(&self, event: MarketEvent) {
+        let senders: Vec<Arc<Sender<StreamEvent<MarketEvent>>>> = self
+            .lock()
+            .by_id
+            .values()
+            .filter(|subscriber| subscriber.symbols.contains(event.symbol()))
+            .map(|subscriber| subscriber.sender.clone())
+            .collect();
+        for sender in senders {
+            // A failed send means the subscriber was dropped and is unregistering.
+            let _ = sender.send(StreamEvent::Event(event.clone())).await;
+        }
+    }
+
+    /// Sends a change in the state of the connection to every subscriber.
+    async fn broadcast(&self, lifecycle: &StreamEvent<MarketEvent>) {
+        let senders: Vec<Arc<Sender<StreamEvent<MarketEvent>>>> = self
+            .lock()
+            .by_id
+            .values()
+            .map(|subscriber| subscriber.sender.clone())
+            .collect();
+        for sender in senders {
+            let _ = sender.send(copy_lifecycle(lifecycle)).await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Subscribers> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Returns a copy of a lifecycle event for one subscriber, replacing the error of a
+/// disconnection with its message.
+fn copy_lifecycle(lifecycle: &StreamEvent<MarketEvent>) -> StreamEvent<MarketEvent> {
+    match lifecycle {
+        StreamEvent::Event(event) => StreamEvent::Event(event.clone()),
+        StreamEvent::Disconnected(error) => {
+            StreamEvent::Disconnected(error.as_ref().map(|error| match error {
+                Error::UnexpectedError(message) => Error::UnexpectedError(message.clone()),
+                other => Error::UnexpectedError(other.to_string()),
+            }))
+        }
+        StreamEvent::Reconnecting { attempt, delay } => StreamEvent::Reconnecting {
+            attempt: *attempt,
+            delay: *delay,
+        },
+        StreamEvent::Reconnected {
+            attempt,
+            session_renewed,
+        } => StreamEvent::Reconnected {
+            attempt: *attempt,
+            session_renewed: *session_renewed,
+        },
+    }
+}
+
+/// Waits for the first symbol, opens the stream and routes its events until the policy gives
+/// up.
+async fn run(
+    session: MarketSession<'static>,
+    config: Config,
+    filters: Option<Vec<MarketSessionFilter>>,
+    policy: ReconnectPolicy,
+    shared: Arc<Shared>,
+    mut union: watch::Receiver<Vec<String>>,
+) {
+    let symbols = match union.wait_for(|symbols| !symbols.is_empty()).await {
+        Ok(symbols) => symbols.clone(),
+        Err(_) => return,
+    };
+    let session_id = session.get_session_id().to_owned();
+    let payload = MarketSessionPayload::builder()
+        .symbols(&symbols)
+        .maybe_filters(filters.as_deref())
+        .session_id(&session_id)
+        .linebreak(true)
+        .build();
+    let (events, handle) = session.subscribe_retrying(&config, payload, policy);
+    route(events, &shared, union, &handle).await;
+    error!("The multiplexed market stream gave up reconnecting");
+    shared.close();
+}
+
+async fn route<S>(
+    events: S,
+    shared: &Shared,
+    mut union: watch::Receiver<Vec<String>>,
+    handle: &SubscriptionHandle,
+) where
+    S: Stream<Item = Result<StreamEvent<MarketEvent>>>,
+{
+    let mut events = pin!(events);
+    loop {
+        tokio::select! {
+            item = events.next() => match item {
+                Some(Ok(StreamEvent::Event(event))) => shared.dispatch(event).await,
+                Some(Ok(lifecycle)) => {
+                    info!("Multiplexed market stream: {:?}", lifecycle);
+                    shared.broadcast(&lifecycle).await;
+                }
+                Some(Err(e)) => warn!("Multiplexed market stream: {}", e),
+                None => return,
+            },
+            Ok(()) = union.changed() => {
+                let symbols = union.borrow_and_update().clone();
+                // Tradier requires at least one symbol, so the last one stays subscribed
+                // until a subscriber needs another.
+                if !symbols.is_empty() {
+                    handle.set_symbols(symbols);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn shared(
+        capacity: usize,
+        backpressure: BackpressurePolicy,
+    ) -> (Arc<Shared>, watch::Receiver<Vec<String>>) {
+        let (union, updates) = watch::channel(Vec::new());
+        let shared = Arc::new(Shared {
+            subscribers: Mutex::new(Subscribers::default()),
+            next_id: AtomicU64::new(0),
+            capacity,
+            backpressure,
+            union,
+        });
+        (shared, updates)
+    }
+
+    fn summary(symbol: &str) -> MarketEvent {
+        serde_json::from_str(&format!(r#"{{"type":"summary","symbol":"{symbol}"}}"#)).unwrap()
+    }
+
+    /// Describes a received item as the symbol of its event, or the name of its lifecycle
+    /// change.
+    fn label(item: StreamEvent<MarketEvent>) -> String {
+        match item {
+            StreamEvent::Event(event) => event.symbol().to_owned(),
+            StreamEvent::Disconnected(Some(error)) => format!("disconnected: {error}"),
+            StreamEvent::Disconnected(None) => "disconnected".to_owned(),
+            StreamEvent::Reconnecting { attempt, .. } => format!("reconnecting {attempt}"),
+            StreamEvent::Reconnected { attempt, .. } => format!("reconnected {attempt}"),
+        }
+    }
+
+    #[test]
+    fn test_union_follows_subscribers() {
+        let (shared, union) = shared(8, BackpressurePolicy::Block);
+        let first = Shared::register(&shared, ["SPY".to_owned()].into());
+        let second = Shared::register(&shared, ["AAPL".to_owned(), "SPY".to_owned()].into());
+        assert_eq!(*union.borrow(), vec!["AAPL", "SPY"]);
+
+        second.add_symbols(["C"]);
+        first.remove_symbols(["SPY"]);
+        assert_eq!(second.symbols(), vec!["AAPL", "C", "SPY"]);
+        assert_eq!(*union.borrow(), vec!["AAPL", "C", "SPY"]);
+
+        drop(second);
+        assert!(union.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_routes_events_to_interested_subscribers_only() {
+        let (shared, union) = shared(8, BackpressurePolicy::Block);
+        let mut spy = Shared::register(&shared, ["SPY".to_owned()].into());
+        let mut both = Shared::register(&shared, ["SPY".to_owned(), "C".to_owned()].into());
+        let symbols = ["SPY".to_owned()];
+        let (handle, subscription) = SubscriptionHandle::new(
+            MarketSessionPayload::recommended(&symbols, "session").into_owned(),
+        );
+
+        let events = stream::iter(vec![
+            Ok(StreamEvent::Event(summary("SPY"))),
+            Ok(StreamEvent::Event(summary("C"))),
+            Ok(StreamEvent::Event(summary("MSFT"))),
+        ]);
+        route(events, &shared, union, &handle).await;
+        shared.close();
+
+        assert_eq!(label(spy.recv().await.unwrap()), "SPY");
+        assert!(spy.recv().await.is_none());
+        let received: Vec<_> = (&mut both).map(label).collect().await;
+        assert_eq!(received, vec!["SPY", "C"]);
+        assert_eq!(subscription.borrow().symbols, vec!["C", "SPY"]);
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscribers_do_not_block_with_conflation() {
+        let (shared, _union) = shared(1, BackpressurePolicy::ConflateLatest);
+        let slow = Shared::register(&shared, ["SPY".to_owned(), "C".to_owned()].into());
+        for symbol in ["SPY", "SPY", "C", "SPY"] {
+            shared.dispatch(summary(symbol)).await;
+        }
+        shared.close();
+
+        assert_eq!(slow.counters().conflated(), 1);
+        assert_eq!(slow.counters().dropped(), 2);
+        let received: Vec<_> = slow.map(label).collect().await;
+        assert_eq!(received, vec!["SPY"]);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_reach_every_subscriber() {
+        let (shared, union) = shared(8, BackpressurePolicy::DropOldest);
+        let spy = Shared::register(&shared, ["SPY".to_owned()].into());
+        let citi = Shared::register(&shared, ["C".to_owned()].into());
+        let symbols = ["SPY".to_owned()];
+        let (handle, _subscription) = SubscriptionHandle::new(
+            MarketSessionPayload::recommended(&symbols, "session").into_owned(),
+        );
+
+        let events = stream::iter(vec![
+            Ok(StreamEvent::Event(summary("SPY"))),
+            Ok(StreamEvent::Disconnected(Some(Error::UnexpectedError(
+                "reset".to_owned(),
+            )))),
+            Ok(StreamEvent::Reconnecting {
+                attempt: 1,
+                delay: std::time::Duration::ZERO,
+            }),
+            Ok(StreamEvent::Reconnected {
+                attempt: 1,
+                session_renewed: false,
+            }),
+            Ok(StreamEvent::Event(summary("C"))),
+        ]);
+        route(events, &shared, union, &handle).await;
+        shared.close();
+
+        let gap = [
+            "disconnected: Unexpected error: reset",
+            "reconnecting 1",
+            "reconnected 1",
+        ];
+        let received: Vec<_> = spy.map(label).collect().await;
+        assert_eq!(received, [&["SPY"][..], &gap].concat());
+        let received: Vec<_> = citi.map(label).collect().await;
+        assert_eq!(received, [&gap[..], &["C"]].concat());
+    }
+
+    #[tokio::test]
+    async fn test_union_changes_update_the_subscription() {
+        let (shared, union) = shared(8, BackpressurePolicy::Block);
+        let symbols = ["SPY".to_owned()];
+        let (handle, subscription) = SubscriptionHandle::new(
+            MarketSessionPayload::recommended(&symbols, "session").into_owned(),
+        );
+        let subscriber = Shared::register(&shared, ["SPY".to_owned(), "AAPL".to_owned()].into());
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let routing = {
+            let shared = shared.clone();
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                let events = receiver_stream(receiver);
+                route(events, &shared, union, &handle).await;
+            })
+        };
+        let mut updates = subscription.clone();
+        updates
+            .wait_for(|payload| payload.symbols.len() == 2)
+            .await
+            .unwrap();
+        assert_eq!(handle.symbols(), vec!["AAPL", "SPY"]);
+
+        drop(subscriber);
+        drop(sender);
+        routing.await.unwrap();
+        assert_eq!(handle.symbols(), vec!["AAPL", "SPY"]);
+    }
+
+    fn receiver_stream(
+        mut receiver: mpsc::UnboundedReceiver<Result<StreamEvent<MarketEvent>>>,
+    ) -> impl Stream<Item = Result<StreamEvent<MarketEvent>>> {
+        stream::poll_fn(move |cx| receiver.poll_recv(cx))
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_of_a_dropped_multiplexer_are_closed() {
+        let (shared, _union) = shared(8, BackpressurePolicy::Block);
+        shared.close();
+        let mut late = Shared::register(&shared, ["SPY".to_owned()].into());
+        assert!(late.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_multiplexes_a_market_session() {
+        use futures_util::SinkExt;
+        use tungstenite::Message;
+
+        let ws_address = ("127.0.0.1", 9995u16);
+        let mut server = mockito::Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://{}:{}/v1/markets/events","sessionid":"shared"}}}}"#,
+                ws_address.0, ws_address.1
+            ))
+            .create_async()
+            .await;
+        let listener = tokio::net::TcpListener::bind(ws_address).await.unwrap();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            while let Some(Ok(Message::Text(payload))) = websocket.next().await {
+                if payload.contains(r#""symbols":["C","SPY"]"#) {
+                    break;
+                }
+            }
+            for symbol in ["SPY", "C", "MSFT"] {
+                let event = format!(r#"{{"type":"summary","symbol":"{symbol}"}}"#);
+                websocket.send(Message::Text(event.into())).await.unwrap();
+            }
+            while websocket.next().await.is_some() {}
+        });
+
+        let config = crate::utils::tests::create_test_config()
+            .server_url(&server.url())
+            .finish();
+        let session = MarketSession::new(&config).await.unwrap();
+        let multiplexer = MarketMultiplexer::builder()
+            .session(session)
+            .config(&config)
+            .capacity(4)
+            .build();
+        let mut spy = multiplexer.subscribe(["SPY"]);
+        let mut citi = multiplexer.subscribe(["C"]);
+        assert_eq!(multiplexer.symbols(), vec!["C", "SPY"]);
+
+        assert_eq!(label(spy.recv().await.unwrap()), "SPY");
+        assert_eq!(label(citi.recv().await.unwrap()), "C");
+
+        drop(multiplexer);
+        assert!(spy.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retries_opening_the_stream() {
+        use futures_util::SinkExt;
+        use tungstenite::Message;
+
+        let ws_address = ("127.0.0.1", 9991u16);
+        let mut server = mockito::Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://{}:{}/v1/markets/events","sessionid":"shared"}}}}"#,
+                ws_address.0, ws_address.1
+            ))
+            .create_async()
+            .await;
+        let listener = tokio::net::TcpListener::bind(ws_address).await.unwrap();
+        tokio::spawn(async move {
+            // The first connection is closed before the handshake.
+            drop(listener.accept().await.unwrap());
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            websocket.next().await;
+            let event = r#"{"type":"summary","symbol":"SPY"}"#;
+            websocket.send(Message::Text(event.into())).await.unwrap();
+            while websocket.next().await.is_some() {}
+        });
+
+        let config = crate::utils::tests::create_test_config()
+            .server_url(&server.url())
+            .finish();
+        let session = MarketSession::new(&config).await.unwrap();
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(std::time::Duration::from_millis(1))
+            .jitter(0.0)
+            .max_attempts(3)
+            .build();
+        let multiplexer = MarketMultiplexer::builder()
+            .session(session)
+            .config(&config)
+            .policy(policy)
+            .build();
+        let mut spy = multiplexer.subscribe(["SPY"]);
+
+        assert_eq!(label(spy.recv().await.unwrap()), "reconnecting 1");
+        assert_eq!(label(spy.recv().await.unwrap()), "reconnected 1");
+        assert_eq!(label(spy.recv().await.unwrap()), "SPY");
+    }
+}
@@ -0,0 +1,269 @@
+//! The latest market state of every symbol, built from a market event stream.
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use futures_util::{Stream, StreamExt};
+use tokio::sync::watch;
+
+use crate::wssession::{MarketEvent, Quote, Summary, Trade};
+
+/// The latest known state of a symbol in a [`QuoteBook`].
+///
+/// Fields are `None` until the corresponding event was received.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteSnapshot {
+    pub symbol: String,
+    /// Last bid and ask, with their sizes and exchanges.
+    pub quote: Option<Quote>,
+    /// Last trade, from either `trade` or `tradex` events.
+    pub last_trade: Option<Trade>,
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    pub prev_close: Option<f64>,
+    /// Cumulative volume of the day, as reported with the last trade.
+    pub volume: Option<u64>,
+}
+
+impl QuoteSnapshot {
+    fn new(symbol: &str) -> Self {
+        QuoteSnapshot {
+            symbol: symbol.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the midpoint between the last bid and ask.
+    pub fn mid(&self) -> Option<f64> {
+        self.quote
+            .as_ref()
+            .map(|quote| (quote.bid + quote.ask) / 2.0)
+    }
+
+    /// Returns the difference between the last ask and bid.
+    pub fn spread(&self) -> Option<f64> {
+        self.quote.as_ref().map(|quote| quote.ask - quote.bid)
+    }
+
+    fn apply_trade(&mut self, trade: &Trade) {
+        self.open.get_or_insert(trade.price);
+        self.high = Some(self.high.map_or(trade.price, |high| high.max(trade.price)));
+        self.low = Some(self.low.map_or(trade.price, |low| low.min(trade.price)));
+        self.close = Some(trade.price);
+        if trade.cumulative_volume.is_some() {
+            self.volume = trade.cumulative_volume;
+        }
+        self.last_trade = Some(trade.clone());
+    }
+
+    fn apply_summary(&mut self, summary: &Summary) {
+        self.open = summary.open.or(self.open);
+        self.high = summary.high.or(self.high);
+        self.low = summary.low.or(self.low);
+        self.close = summary.close.or(self.close);
+        self.prev_close = summary.prev_close.or(self.prev_close);
+    }
+}
+
+/// A concurrent cache of the latest quote, trade and daily summary of every symbol.
+///
+/// The book consumes typed [`MarketEvent`] values, from [`QuoteBook::apply`] or
+/// [`QuoteBook::consume`], and keeps one [`QuoteSnapshot`] per symbol. Trades also extend the
+/// day's open, high, low and close until the next summary event corrects them. Time and sale
+/// events are ignored.
+///
+/// Clones share the same book, so one task can feed it while any number of others read
+/// [`snapshot`](QuoteBook::snapshot)s or [`watch`](QuoteBook::watch) a symbol for changes.
+#[derive(Debug, Clone, Default)]
+pub struct QuoteBook {
+    symbols: Arc<RwLock<HashMap<String, watch::Sender<QuoteSnapshot>>>>,
+}
+
+impl QuoteBook {
+    /// Constructs an empty `QuoteBook`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the snapshot of the event's symbol. Returns `true` if the snapshot changed, in
+    /// which case the watchers of the symbol are notified.
+    pub fn apply(&self, event: &MarketEvent) -> bool {
+        if matches!(event, MarketEvent::Timesale(_)) {
+            return false;
+        }
+        let update = |snapshot: &mut QuoteSnapshot| {
+            let before = snapshot.clone();
+            match event {
+                MarketEvent::Quote(quote) => snapshot.quote = Some(quote.clone()),
+                MarketEvent::Trade(trade) | MarketEvent::Tradex(trade) => {
+                    snapshot.apply_trade(trade)
+                }
+                MarketEvent::Summary(summary) => snapshot.apply_summary(summary),
+                MarketEvent::Timesale(_) => {}
+            }
+            *snapshot != before
+        };
+        self.with_sender(event.symbol(), |sender| sender.send_if_modified(update))
+    }
+
+    /// Applies every event of `events` until the stream ends.
+    pub async fn consume<S>(&self, events: S)
+    where
+        S: Stream<Item = MarketEvent>,
+    {
+        let mut events = std::pin::pin!(events);
+        while let Some(event) = events.next().await {
+            self.apply(&event);
+        }
+    }
+
+    /// Returns the latest state of `symbol`, or `None` if no event was received for it.
+    pub fn snapshot(&self, symbol: &str) -> Option<QuoteSnapshot> {
+        self.read()
+            .get(symbol)
+            .map(|sender| sender.borrow().clone())
+            .filter(|snapshot| *snapshot != QuoteSnapshot::new(symbol))
+    }
+
+    /// Returns the latest state of every symbol an event was received for, sorted by symbol.
+    pub fn snapshots(&self) -> Vec<QuoteSnapshot> {
+        let mut snapshots: Vec<QuoteSnapshot> = self
+            .read()
+            .values()
+            .map(|sender| sender.borrow().clone())
+            .filter(|snapshot| *snapshot != QuoteSnapshot::new(&snapshot.symbol))
+            .collect();
+        snapshots.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        snapshots
+    }
+
+    /// Returns a receiver that is notified every time the snapshot of `symbol` changes. The
+    /// symbol does not need to have received an event yet; its snapshot is then empty.
+    pub fn watch(&self, symbol: &str) -> watch::Receiver<QuoteSnapshot> {
+        self.with_sender(symbol, watch::Sender::subscribe)
+    }
+
+    fn with_sender<R>(
+        &self,
+        symbol: &str,
+        f: impl FnOnce(&watch::Sender<QuoteSnapshot>) -> R,
+    ) -> R {
+        if let Some(sender) = self.read().get(symbol) {
+            return f(sender);
+        }
+        let mut symbols = self
+            .symbols
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let sender = symbols
+            .entry(symbol.to_owned())
+            .or_insert_with(|| watch::Sender::new(QuoteSnapshot::new(symbol)));
+        f(sender)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, watch::Sender<QuoteSnapshot>>> {
+        self.symbols
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+
+    fn event(json: &str) -> MarketEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn quote(symbol: &str, bid: f64, ask: f64) -> MarketEvent {
+        event(&format!(
+            r#"{{"type":"quote","symbol":"{symbol}","bid":{bid},"bidsz":5,"bidexch":"Q","biddate":"1557757189000","ask":{ask},"asksz":7,"askexch":"P","askdate":"1557757189000"}}"#
+        ))
+    }
+
+    fn trade(symbol: &str, price: f64, cvol: u64) -> MarketEvent {
+        event(&format!(
+            r#"{{"type":"trade","symbol":"{symbol}","exch":"Q","price":"{price}","size":"100","cvol":"{cvol}","date":"1557757189000","last":"{price}"}}"#
+        ))
+    }
+
+    #[test]
+    fn test_quotes_and_trades_update_the_snapshot() {
+        let book = QuoteBook::new();
+        assert!(book.apply(&quote("SPY", 281.84, 281.86)));
+        assert!(book.apply(&trade("SPY", 281.85, 1000)));
+        assert!(book.apply(&trade("SPY", 282.10, 1100)));
+        assert!(book.apply(&trade("SPY", 281.50, 1200)));
+
+        let snapshot = book.snapshot("SPY").unwrap();
+        let quote = snapshot.quote.as_ref().unwrap();
+        assert_eq!(
+            (quote.bid, quote.bid_size, quote.bid_exchange.as_str()),
+            (281.84, 5, "Q")
+        );
+        assert_eq!(
+            (quote.ask, quote.ask_size, quote.ask_exchange.as_str()),
+            (281.86, 7, "P")
+        );
+        assert_eq!(snapshot.last_trade.as_ref().unwrap().price, 281.50);
+        assert_eq!(snapshot.open, Some(281.85));
+        assert_eq!(snapshot.high, Some(282.10));
+        assert_eq!(snapshot.low, Some(281.50));
+        assert_eq!(snapshot.close, Some(281.50));
+        assert_eq!(snapshot.volume, Some(1200));
+        assert!((snapshot.mid().unwrap() - 281.85).abs() < 1e-9);
+        assert!(book.snapshot("C").is_none());
+    }
+
+    #[test]
+    fn test_summary_corrects_the_day_range() {
+        let book = QuoteBook::new();
+        book.apply(&trade("SPY", 281.85, 1000));
+        book.apply(&event(
+            r#"{"type":"summary","symbol":"SPY","open":"280.00","high":"283.00","low":"279.50","prevClose":"288.9"}"#,
+        ));
+
+        let snapshot = book.snapshot("SPY").unwrap();
+        assert_eq!(snapshot.open, Some(280.00));
+        assert_eq!(snapshot.high, Some(283.00));
+        assert_eq!(snapshot.low, Some(279.50));
+        assert_eq!(snapshot.close, Some(281.85));
+        assert_eq!(snapshot.prev_close, Some(288.9));
+    }
+
+    #[test]
+    fn test_unchanged_snapshots_are_not_notified() {
+        let book = QuoteBook::new();
+        let mut spy = book.watch("SPY");
+        assert!(book.snapshots().is_empty());
+
+        book.apply(&quote("SPY", 1.0, 2.0));
+        assert!(spy.has_changed().unwrap());
+        spy.mark_unchanged();
+
+        assert!(!book.apply(&quote("SPY", 1.0, 2.0)));
+        book.apply(&quote("C", 3.0, 4.0));
+        assert!(!spy.has_changed().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_watchers_see_consumed_events() {
+        let book = QuoteBook::new();
+        let mut spy = book.watch("SPY");
+
+        let feeder = book.clone();
+        tokio::spawn(async move {
+            let events = stream::iter(vec![quote("C", 3.0, 4.0), quote("SPY", 1.0, 2.0)]);
+            feeder.consume(events).await;
+        });
+
+        spy.changed().await.unwrap();
+        assert_eq!(spy.borrow_and_update().quote.as_ref().unwrap().bid, 1.0);
+        let symbols: Vec<String> = book.snapshots().into_iter().map(|s| s.symbol).collect();
+        assert_eq!(symbols, vec!["C", "SPY"]);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::accounts::types::AccountNumber;
+use crate::trading::types::{OrderModification, OrderPreview, OrderRequest, OrderResponse};
+use crate::{error::Result, utils::Sealed};
+
+pub mod non_blocking {
+    use super::*;
+
+    #[async_trait::async_trait]
+    pub trait Trading: Sealed {
+        async fn place_order<O>(
+            &self,
+            account_number: &AccountNumber,
+            order: &O,
+        ) -> Result<OrderResponse>
+        where
+            O: OrderRequest + Sync;
+
+        /// Submits `order` with `preview=true`, returning its estimated cost and margin impact
+        /// without sending it to the market.
+        async fn preview_order<O>(
+            &self,
+            account_number: &AccountNumber,
+            order: &O,
+        ) -> Result<OrderPreview>
+        where
+            O: OrderRequest + Sync;
+
+        /// Changes the type, duration, price or stop of an open order.
+        ///
+        /// Fails with [`Error::OrderNotModifiable`](crate::Error::OrderNotModifiable) if
+        /// Tradier reports that the order can no longer be modified.
+        async fn modify_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+            modification: &OrderModification,
+        ) -> Result<OrderResponse>;
+
+        /// Cancels an open order.
+        ///
+        /// Fails with [`Error::OrderNotCancelable`](crate::Error::OrderNotCancelable) if
+        /// Tradier reports that the order can no longer be canceled.
+        async fn cancel_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+        ) -> Result<OrderResponse>;
+    }
+}
+pub mod blocking {
+    use super::*;
+
+    pub trait Trading: Sealed {
+        fn place_order<O>(
+            &self,
+            account_number: &AccountNumber,
+            order: &O,
+        ) -> Result<OrderResponse>
+        where
+            O: OrderRequest + Sync;
+
+        /// Submits `order` with `preview=true`, returning its estimated cost and margin impact
+        /// without sending it to the market.
+        fn preview_order<O>(
+            &self,
+            account_number: &AccountNumber,
+            order: &O,
+        ) -> Result<OrderPreview>
+        where
+            O: OrderRequest + Sync;
+
+        /// Changes the type, duration, price or stop of an open order.
+        ///
+        /// Fails with [`Error::OrderNotModifiable`](crate::Error::OrderNotModifiable) if
+        /// Tradier reports that the order can no longer be modified.
+        fn modify_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+            modification: &OrderModification,
+        ) -> Result<OrderResponse>;
+
+        /// Cancels an open order.
+        ///
+        /// Fails with [`Error::OrderNotCancelable`](crate::Error::OrderNotCancelable) if
+        /// Tradier reports that the order can no longer be canceled.
+        fn cancel_order(
+            &self,
+            account_number: &AccountNumber,
+            order_id: u64,
+        ) -> Result<OrderResponse>;
+    }
+}
@@ -1 +1,4 @@
-
+pub mod api;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod types;
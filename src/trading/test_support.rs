@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OrderResponseEnvelopeWire {
+    order: OrderResponseWire,
+}
+
+#[derive(Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OrderResponseWire {
+    id: u64,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partner_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OrderPreviewResponseWire {
+    order: OrderPreviewWire,
+}
+
+#[derive(Debug, Serialize, proptest_derive::Arbitrary)]
+pub struct OrderPreviewWire {
+    status: String,
+    result: bool,
+    commission: f64,
+    cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fees: Option<f64>,
+    order_cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    margin_change: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day_trades: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extended_hours: Option<bool>,
+    warnings: Vec<String>,
+}
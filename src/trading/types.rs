@@ -0,0 +1,2131 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    utils::{
+        deserializers::{option_f64_lenient, vec_from_one_or_many},
+        Sealed,
+    },
+    Error, Result,
+};
+
+/// An order that can be submitted through `POST /v1/accounts/{account_id}/orders`.
+///
+/// Implementors are validated when they are built, so every value of an `OrderRequest` type
+/// describes an order Tradier can accept.
+pub trait OrderRequest: Sealed {
+    /// Returns the form fields submitted to Tradier for this order.
+    fn form_params(&self) -> Vec<(String, String)>;
+}
+
+/// The class of an order, sent as the `class` form field.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum OrderClass {
+    Equity,
+    Option,
+    Multileg,
+    Combo,
+    Oto,
+    Oco,
+    Otoco,
+}
+
+impl Display for OrderClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OrderClass::Equity => "equity",
+            OrderClass::Option => "option",
+            OrderClass::Multileg => "multileg",
+            OrderClass::Combo => "combo",
+            OrderClass::Oto => "oto",
+            OrderClass::Oco => "oco",
+            OrderClass::Otoco => "otoco",
+        };
+        f.write_str(value)
+    }
+}
+
+/// The side of an equity order.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OrderSide {
+    Buy,
+    BuyToCover,
+    Sell,
+    SellShort,
+}
+
+impl Display for OrderSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OrderSide::Buy => "buy",
+            OrderSide::BuyToCover => "buy_to_cover",
+            OrderSide::Sell => "sell",
+            OrderSide::SellShort => "sell_short",
+        };
+        f.write_str(value)
+    }
+}
+
+/// The side of an option order.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OptionSide {
+    BuyToOpen,
+    BuyToClose,
+    SellToOpen,
+    SellToClose,
+}
+
+impl Display for OptionSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OptionSide::BuyToOpen => "buy_to_open",
+            OptionSide::BuyToClose => "buy_to_close",
+            OptionSide::SellToOpen => "sell_to_open",
+            OptionSide::SellToClose => "sell_to_close",
+        };
+        f.write_str(value)
+    }
+}
+
+/// Whether an option contract is a call or a put.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl Display for OptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OptionType::Call => "call",
+            OptionType::Put => "put",
+        };
+        f.write_str(value)
+    }
+}
+
+/// An option contract identified by its OCC symbol, e.g. `AAPL240119C00150000`.
+///
+/// The OCC format is the underlying root (up to six characters), the expiration date as
+/// `YYMMDD`, `C` or `P`, and the strike price multiplied by 1000 and zero-padded to eight
+/// digits. Tradier uses the compact form without the space padding of the root.
+///
+/// # Example
+/// ```
+/// use tradier::types::{OptionSymbol, OptionType};
+///
+/// let symbol: OptionSymbol = "AAPL240119C00150000".parse().expect("a valid OCC symbol");
+/// assert_eq!(symbol.root(), "AAPL");
+/// assert_eq!(symbol.option_type(), OptionType::Call);
+/// assert_eq!(symbol.strike(), 150.0);
+/// assert_eq!(symbol.to_string(), "AAPL240119C00150000");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OptionSymbol {
+    root: String,
+    expiration: NaiveDate,
+    option_type: OptionType,
+    /// Strike price in thousandths of a dollar, as encoded in the OCC symbol.
+    strike: u32,
+}
+
+impl OptionSymbol {
+    /// Length of the date, type and strike portion that follows the root.
+    const SUFFIX_LEN: usize = 15;
+    const MAX_ROOT_LEN: usize = 6;
+
+    /// Constructs an `OptionSymbol` from its parts.
+    ///
+    /// # Errors
+    /// Returns [`Error::OptionSymbolParseError`] if the root is not one to six alphanumeric
+    /// characters, the expiration year is outside 2000..=2099, or the strike cannot be encoded
+    /// in eight digits of thousandths.
+    pub fn new(
+        root: &str,
+        expiration: NaiveDate,
+        option_type: OptionType,
+        strike: f64,
+    ) -> Result<Self> {
+        let invalid =
+            || Error::OptionSymbolParseError(format!("{root} {expiration} {option_type} {strike}"));
+        let thousandths = (strike * 1000.0).round();
+        if !(strike.is_finite() && (1.0..100_000_000.0).contains(&thousandths)) {
+            return Err(invalid());
+        }
+        if !(2000..=2099).contains(&expiration.year()) || !Self::is_valid_root(root) {
+            return Err(invalid());
+        }
+        Ok(OptionSymbol {
+            root: root.to_owned(),
+            expiration,
+            option_type,
+            strike: thousandths as u32,
+        })
+    }
+
+    fn is_valid_root(root: &str) -> bool {
+        !root.is_empty()
+            && root.len() <= Self::MAX_ROOT_LEN
+            && root.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    /// The underlying root symbol, e.g. `AAPL`.
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// The expiration date of the contract.
+    pub fn expiration(&self) -> NaiveDate {
+        self.expiration
+    }
+
+    /// Whether the contract is a call or a put.
+    pub fn option_type(&self) -> OptionType {
+        self.option_type
+    }
+
+    /// The strike price in dollars.
+    pub fn strike(&self) -> f64 {
+        f64::from(self.strike) / 1000.0
+    }
+}
+
+impl FromStr for OptionSymbol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::OptionSymbolParseError(s.to_owned());
+        if !s.is_ascii() || s.len() <= Self::SUFFIX_LEN {
+            return Err(invalid());
+        }
+        let (root, suffix) = s.split_at(s.len() - Self::SUFFIX_LEN);
+        let root = root.trim_end();
+        if !Self::is_valid_root(root) {
+            return Err(invalid());
+        }
+
+        let (date, rest) = suffix.split_at(6);
+        let (option_type, strike) = rest.split_at(1);
+        if !date
+            .bytes()
+            .chain(strike.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let expiration =
+            NaiveDate::parse_from_str(&format!("20{date}"), "%Y%m%d").map_err(|_| invalid())?;
+        let option_type = match option_type {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            _ => return Err(invalid()),
+        };
+        let strike = strike.parse::<u32>().map_err(|_| invalid())?;
+        if strike == 0 {
+            return Err(invalid());
+        }
+
+        Ok(OptionSymbol {
+            root: root.to_owned(),
+            expiration,
+            option_type,
+            strike,
+        })
+    }
+}
+
+impl Display for OptionSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option_type = match self.option_type {
+            OptionType::Call => 'C',
+            OptionType::Put => 'P',
+        };
+        write!(
+            f,
+            "{}{}{}{:08}",
+            self.root,
+            self.expiration.format("%y%m%d"),
+            option_type,
+            self.strike
+        )
+    }
+}
+
+impl TryFrom<String> for OptionSymbol {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<OptionSymbol> for String {
+    fn from(value: OptionSymbol) -> Self {
+        value.to_string()
+    }
+}
+
+/// The pricing type of an order.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OrderType {
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+}
+
+impl Display for OrderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OrderType::Market => "market",
+            OrderType::Limit => "limit",
+            OrderType::Stop => "stop",
+            OrderType::StopLimit => "stop_limit",
+        };
+        f.write_str(value)
+    }
+}
+
+/// How long an order remains active.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum OrderDuration {
+    Day,
+    Gtc,
+    Pre,
+    Post,
+}
+
+impl Display for OrderDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            OrderDuration::Day => "day",
+            OrderDuration::Gtc => "gtc",
+            OrderDuration::Pre => "pre",
+            OrderDuration::Post => "post",
+        };
+        f.write_str(value)
+    }
+}
+
+/// Checks that `price` and `stop` are present exactly when `order_type` requires them.
+fn validate_pricing(order_type: OrderType, price: Option<f64>, stop: Option<f64>) -> Result<()> {
+    let (needs_price, needs_stop) = match order_type {
+        OrderType::Market => (false, false),
+        OrderType::Limit => (true, false),
+        OrderType::Stop => (false, true),
+        OrderType::StopLimit => (true, true),
+    };
+    for (name, value, needed) in [("price", price, needs_price), ("stop", stop, needs_stop)] {
+        match value {
+            Some(v) if !needed => {
+                return Err(Error::InvalidOrder(format!(
+                    "{order_type} orders do not accept a {name}, got {v}"
+                )))
+            }
+            None if needed => {
+                return Err(Error::InvalidOrder(format!(
+                    "{order_type} orders require a {name}"
+                )))
+            }
+            _ => validate_price(name, value)?,
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a price, when set, is a positive number on the tick grid.
+fn validate_price(name: &str, value: Option<f64>) -> Result<()> {
+    match value {
+        Some(v) if !(v.is_finite() && v > 0.0) => Err(Error::InvalidOrder(format!(
+            "{name} must be a positive number, got {v}"
+        ))),
+        Some(v) => validate_tick(name, v),
+        None => Ok(()),
+    }
+}
+
+/// Number of decimals of the tick size of `price`: two from $1, four below.
+fn price_decimals(price: f64) -> usize {
+    if price.abs() >= 1.0 {
+        2
+    } else {
+        4
+    }
+}
+
+/// Rejects prices with more decimals than the tick size allows, which would otherwise be
+/// rounded to a price the caller never asked for. Floating point noise such as
+/// `0.30000000000000004` is tolerated.
+fn validate_tick(name: &str, price: f64) -> Result<()> {
+    let decimals = price_decimals(price);
+    let ticks = price * 10f64.powi(decimals as i32);
+    if (ticks - ticks.round()).abs() > 1e-6 {
+        return Err(Error::InvalidOrder(format!(
+            "{name} {price} has more than {decimals} decimals"
+        )));
+    }
+    Ok(())
+}
+
+/// Formats a validated price for a form field with the decimals of its tick size, so that
+/// floating point noise such as `0.30000000000000004` is never sent.
+fn format_price(price: f64) -> String {
+    format!("{price:.*}", price_decimals(price))
+}
+
+fn validate_quantity(quantity: u32) -> Result<()> {
+    if quantity == 0 {
+        return Err(Error::InvalidOrder(
+            "quantity must be greater than zero".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// A single-leg equity order (`class=equity`).
+///
+/// # Example
+/// ```
+/// use tradier::types::{EquityOrder, OrderDuration, OrderSide, OrderType};
+///
+/// let order = EquityOrder::builder()
+///     .symbol("AAPL")
+///     .side(OrderSide::Buy)
+///     .quantity(10)
+///     .order_type(OrderType::Limit)
+///     .duration(OrderDuration::Day)
+///     .price(150.0)
+///     .build()
+///     .expect("a limit order with a price to be valid");
+/// # let _ = order;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityOrder {
+    symbol: String,
+    side: OrderSide,
+    quantity: u32,
+    order_type: OrderType,
+    duration: OrderDuration,
+    price: Option<f64>,
+    stop: Option<f64>,
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl EquityOrder {
+    /// Constructs a new `EquityOrder`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the symbol is blank, the quantity is zero, or the
+    /// `price`/`stop` fields do not match the order type (e.g. a `limit` order without a price).
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        #[builder(into)] symbol: String,
+        side: OrderSide,
+        quantity: u32,
+        order_type: OrderType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        stop: Option<f64>,
+        #[builder(into)] tag: Option<String>,
+    ) -> Result<Self> {
+        if symbol.trim().is_empty() {
+            return Err(Error::InvalidOrder("symbol must not be blank".to_owned()));
+        }
+        validate_quantity(quantity)?;
+        validate_pricing(order_type, price, stop)?;
+        Ok(EquityOrder {
+            symbol,
+            side,
+            quantity,
+            order_type,
+            duration,
+            price,
+            stop,
+            tag,
+        })
+    }
+}
+
+impl Sealed for EquityOrder {}
+
+impl OrderRequest for EquityOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![
+            ("class".to_owned(), OrderClass::Equity.to_string()),
+            ("symbol".to_owned(), self.symbol.clone()),
+            ("side".to_owned(), self.side.to_string()),
+            ("quantity".to_owned(), self.quantity.to_string()),
+            ("type".to_owned(), self.order_type.to_string()),
+            ("duration".to_owned(), self.duration.to_string()),
+        ];
+        if let Some(price) = self.price {
+            params.push(("price".to_owned(), format_price(price)));
+        }
+        if let Some(stop) = self.stop {
+            params.push(("stop".to_owned(), format_price(stop)));
+        }
+        if let Some(tag) = &self.tag {
+            params.push(("tag".to_owned(), tag.clone()));
+        }
+        params
+    }
+}
+
+/// A single-leg option order (`class=option`).
+///
+/// The underlying `symbol` field sent to Tradier is derived from the root of the
+/// [`OptionSymbol`], so the contract is validated before the request is made.
+///
+/// # Example
+/// ```
+/// use tradier::types::{OptionOrder, OptionSide, OrderDuration, OrderType};
+///
+/// let order = OptionOrder::builder()
+///     .option_symbol("SPY240119P00450000".parse().expect("a valid OCC symbol"))
+///     .side(OptionSide::BuyToOpen)
+///     .quantity(1)
+///     .order_type(OrderType::Limit)
+///     .duration(OrderDuration::Day)
+///     .price(2.15)
+///     .build()
+///     .expect("a limit order with a price to be valid");
+/// # let _ = order;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionOrder {
+    option_symbol: OptionSymbol,
+    side: OptionSide,
+    quantity: u32,
+    order_type: OrderType,
+    duration: OrderDuration,
+    price: Option<f64>,
+    stop: Option<f64>,
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl OptionOrder {
+    /// Constructs a new `OptionOrder`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the quantity is zero, the duration is not `day` or
+    /// `gtc` (options do not trade in extended hours), or the `price`/`stop` fields do not match
+    /// the order type.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        option_symbol: OptionSymbol,
+        side: OptionSide,
+        quantity: u32,
+        order_type: OrderType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        stop: Option<f64>,
+        #[builder(into)] tag: Option<String>,
+    ) -> Result<Self> {
+        validate_quantity(quantity)?;
+        validate_session_duration("option orders", duration)?;
+        validate_pricing(order_type, price, stop)?;
+        Ok(OptionOrder {
+            option_symbol,
+            side,
+            quantity,
+            order_type,
+            duration,
+            price,
+            stop,
+            tag,
+        })
+    }
+}
+
+/// Checks that `duration` is limited to the regular session, which is all that option and
+/// multi-order requests support.
+fn validate_session_duration(kind: &str, duration: OrderDuration) -> Result<()> {
+    match duration {
+        OrderDuration::Day | OrderDuration::Gtc => Ok(()),
+        _ => Err(Error::InvalidOrder(format!(
+            "{kind} only support day or gtc durations, got {duration}"
+        ))),
+    }
+}
+
+impl Sealed for OptionOrder {}
+
+impl OrderRequest for OptionOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![
+            ("class".to_owned(), OrderClass::Option.to_string()),
+            ("symbol".to_owned(), self.option_symbol.root().to_owned()),
+            ("option_symbol".to_owned(), self.option_symbol.to_string()),
+            ("side".to_owned(), self.side.to_string()),
+            ("quantity".to_owned(), self.quantity.to_string()),
+            ("type".to_owned(), self.order_type.to_string()),
+            ("duration".to_owned(), self.duration.to_string()),
+        ];
+        if let Some(price) = self.price {
+            params.push(("price".to_owned(), format_price(price)));
+        }
+        if let Some(stop) = self.stop {
+            params.push(("stop".to_owned(), format_price(stop)));
+        }
+        if let Some(tag) = &self.tag {
+            params.push(("tag".to_owned(), tag.clone()));
+        }
+        params
+    }
+}
+
+/// The pricing type of a multileg or combo order, which is priced as a net amount.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum NetPriceType {
+    Market,
+    Debit,
+    Credit,
+    Even,
+}
+
+impl Display for NetPriceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            NetPriceType::Market => "market",
+            NetPriceType::Debit => "debit",
+            NetPriceType::Credit => "credit",
+            NetPriceType::Even => "even",
+        };
+        f.write_str(value)
+    }
+}
+
+/// Checks that a net `price` is present exactly when `price_type` requires one.
+fn validate_net_pricing(price_type: NetPriceType, price: Option<f64>) -> Result<()> {
+    match (price_type, price) {
+        (NetPriceType::Debit | NetPriceType::Credit, Some(p)) if p.is_finite() && p > 0.0 => {
+            validate_tick("net price", p)
+        }
+        (NetPriceType::Debit | NetPriceType::Credit, Some(p)) => Err(Error::InvalidOrder(format!(
+            "{price_type} orders require a positive net price, got {p}"
+        ))),
+        (NetPriceType::Debit | NetPriceType::Credit, None) => Err(Error::InvalidOrder(format!(
+            "{price_type} orders require a net price"
+        ))),
+        (NetPriceType::Market | NetPriceType::Even, Some(p)) => Err(Error::InvalidOrder(format!(
+            "{price_type} orders do not accept a price, got {p}"
+        ))),
+        (NetPriceType::Market | NetPriceType::Even, None) => Ok(()),
+    }
+}
+
+/// One option leg of a multileg or combo order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionLeg {
+    option_symbol: OptionSymbol,
+    side: OptionSide,
+    quantity: u32,
+}
+
+impl OptionLeg {
+    /// Constructs a leg that trades `quantity` contracts of `option_symbol`.
+    pub fn new(option_symbol: OptionSymbol, side: OptionSide, quantity: u32) -> Self {
+        OptionLeg {
+            option_symbol,
+            side,
+            quantity,
+        }
+    }
+
+    /// The contract traded by this leg.
+    pub fn option_symbol(&self) -> &OptionSymbol {
+        &self.option_symbol
+    }
+
+    /// The side of this leg.
+    pub fn side(&self) -> OptionSide {
+        self.side
+    }
+
+    /// The number of contracts traded by this leg.
+    pub fn quantity(&self) -> u32 {
+        self.quantity
+    }
+}
+
+/// Checks that every option leg has a quantity and is not repeated.
+///
+/// Legs are not compared against the underlying symbol: OCC roots such as `SPXW` (under `SPX`)
+/// or adjusted roots such as `AAPL1` differ from it, so Tradier is left to match them.
+fn validate_option_legs<'a>(legs: impl IntoIterator<Item = &'a OptionLeg>) -> Result<()> {
+    let mut seen: Vec<&OptionSymbol> = Vec::new();
+    for leg in legs {
+        validate_quantity(leg.quantity)?;
+        if seen.contains(&&leg.option_symbol) {
+            return Err(Error::InvalidOrder(format!(
+                "{} appears in more than one leg",
+                leg.option_symbol
+            )));
+        }
+        seen.push(&leg.option_symbol);
+    }
+    Ok(())
+}
+
+/// A multileg option spread (`class=multileg`) of two to four legs on the same underlying,
+/// such as a vertical spread or an iron condor.
+///
+/// # Example
+/// ```
+/// use tradier::types::{MultilegOrder, NetPriceType, OptionLeg, OptionSide, OrderDuration};
+///
+/// let order = MultilegOrder::builder()
+///     .symbol("SPY")
+///     .price_type(NetPriceType::Debit)
+///     .price(1.25)
+///     .duration(OrderDuration::Day)
+///     .legs(vec![
+///         OptionLeg::new("SPY240119C00450000".parse().unwrap(), OptionSide::BuyToOpen, 1),
+///         OptionLeg::new("SPY240119C00455000".parse().unwrap(), OptionSide::SellToOpen, 1),
+///     ])
+///     .build()
+///     .expect("a debit vertical to be valid");
+/// # let _ = order;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultilegOrder {
+    symbol: String,
+    price_type: NetPriceType,
+    duration: OrderDuration,
+    price: Option<f64>,
+    legs: Vec<OptionLeg>,
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl MultilegOrder {
+    /// The smallest number of legs Tradier accepts for a multileg order.
+    pub const MIN_LEGS: usize = 2;
+    /// The largest number of legs Tradier accepts for a multileg order.
+    pub const MAX_LEGS: usize = 4;
+
+    /// Constructs a new `MultilegOrder`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if there are fewer than two or more than four legs, a leg
+    /// repeats another leg's contract, the duration is not `day` or `gtc`, or `price` does not match `price_type` (`debit` and `credit` need a
+    /// positive net price on the tick grid, `market` and `even` take none).
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        #[builder(into)] symbol: String,
+        price_type: NetPriceType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        legs: Vec<OptionLeg>,
+        #[builder(into)] tag: Option<String>,
+    ) -> Result<Self> {
+        if !(Self::MIN_LEGS..=Self::MAX_LEGS).contains(&legs.len()) {
+            return Err(Error::InvalidOrder(format!(
+                "multileg orders need between {} and {} legs, got {}",
+                Self::MIN_LEGS,
+                Self::MAX_LEGS,
+                legs.len()
+            )));
+        }
+        validate_option_legs(&legs)?;
+        validate_session_duration("multileg orders", duration)?;
+        validate_net_pricing(price_type, price)?;
+        Ok(MultilegOrder {
+            symbol,
+            price_type,
+            duration,
+            price,
+            legs,
+            tag,
+        })
+    }
+}
+
+impl Sealed for MultilegOrder {}
+
+impl OrderRequest for MultilegOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![
+            ("class".to_owned(), OrderClass::Multileg.to_string()),
+            ("symbol".to_owned(), self.symbol.clone()),
+            ("type".to_owned(), self.price_type.to_string()),
+            ("duration".to_owned(), self.duration.to_string()),
+        ];
+        if let Some(price) = self.price {
+            params.push(("price".to_owned(), format_price(price)));
+        }
+        for (index, leg) in self.legs.iter().enumerate() {
+            params.push((
+                format!("option_symbol[{index}]"),
+                leg.option_symbol.to_string(),
+            ));
+            params.push((format!("side[{index}]"), leg.side.to_string()));
+            params.push((format!("quantity[{index}]"), leg.quantity.to_string()));
+        }
+        if let Some(tag) = &self.tag {
+            params.push(("tag".to_owned(), tag.clone()));
+        }
+        params
+    }
+}
+
+/// The equity leg of a combo order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityLeg {
+    side: OrderSide,
+    quantity: u32,
+}
+
+impl EquityLeg {
+    /// Constructs a leg that trades `quantity` shares of the combo's underlying.
+    pub fn new(side: OrderSide, quantity: u32) -> Self {
+        EquityLeg { side, quantity }
+    }
+
+    /// The side of this leg.
+    pub fn side(&self) -> OrderSide {
+        self.side
+    }
+
+    /// The number of shares traded by this leg.
+    pub fn quantity(&self) -> u32 {
+        self.quantity
+    }
+}
+
+/// A leg of a combo order, either the underlying stock or an option on it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComboLeg {
+    Equity(EquityLeg),
+    Option(OptionLeg),
+}
+
+impl From<EquityLeg> for ComboLeg {
+    fn from(value: EquityLeg) -> Self {
+        ComboLeg::Equity(value)
+    }
+}
+
+impl From<OptionLeg> for ComboLeg {
+    fn from(value: OptionLeg) -> Self {
+        ComboLeg::Option(value)
+    }
+}
+
+/// A combo order (`class=combo`) that trades the underlying stock together with options on it,
+/// such as a covered call or a collar.
+///
+/// Exactly one leg must be an [`EquityLeg`]; it is always submitted as the first indexed leg.
+///
+/// # Example
+/// ```
+/// use tradier::types::{
+///     ComboOrder, EquityLeg, NetPriceType, OptionLeg, OptionSide, OrderDuration, OrderSide,
+/// };
+///
+/// let covered_call = ComboOrder::builder()
+///     .symbol("AAPL")
+///     .price_type(NetPriceType::Debit)
+///     .price(148.5)
+///     .duration(OrderDuration::Day)
+///     .legs(vec![
+///         EquityLeg::new(OrderSide::Buy, 100).into(),
+///         OptionLeg::new("AAPL240119C00155000".parse().unwrap(), OptionSide::SellToOpen, 1).into(),
+///     ])
+///     .build()
+///     .expect("a covered call to be valid");
+/// # let _ = covered_call;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComboOrder {
+    symbol: String,
+    price_type: NetPriceType,
+    duration: OrderDuration,
+    price: Option<f64>,
+    equity_leg: EquityLeg,
+    option_legs: Vec<OptionLeg>,
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl ComboOrder {
+    /// The largest number of option legs Tradier accepts alongside the equity leg.
+    pub const MAX_OPTION_LEGS: usize = 2;
+
+    /// Constructs a new `ComboOrder`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if there is not exactly one equity leg, there are no option
+    /// legs or more than two, a leg has a zero quantity, the duration is not `day` or `gtc`, or `price` does not match
+    /// `price_type`.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        #[builder(into)] symbol: String,
+        price_type: NetPriceType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        legs: Vec<ComboLeg>,
+        #[builder(into)] tag: Option<String>,
+    ) -> Result<Self> {
+        let mut equity_legs = Vec::new();
+        let mut option_legs = Vec::new();
+        for leg in legs {
+            match leg {
+                ComboLeg::Equity(leg) => equity_legs.push(leg),
+                ComboLeg::Option(leg) => option_legs.push(leg),
+            }
+        }
+        let equity_leg = match <[EquityLeg; 1]>::try_from(equity_legs) {
+            Ok([leg]) => leg,
+            Err(legs) => {
+                return Err(Error::InvalidOrder(format!(
+                    "combo orders need exactly one equity leg, got {}",
+                    legs.len()
+                )))
+            }
+        };
+        if !(1..=Self::MAX_OPTION_LEGS).contains(&option_legs.len()) {
+            return Err(Error::InvalidOrder(format!(
+                "combo orders need between 1 and {} option legs, got {}",
+                Self::MAX_OPTION_LEGS,
+                option_legs.len()
+            )));
+        }
+        if symbol.trim().is_empty() {
+            return Err(Error::InvalidOrder("symbol must not be blank".to_owned()));
+        }
+        validate_quantity(equity_leg.quantity)?;
+        validate_option_legs(&option_legs)?;
+        validate_session_duration("combo orders", duration)?;
+        validate_net_pricing(price_type, price)?;
+        Ok(ComboOrder {
+            symbol,
+            price_type,
+            duration,
+            price,
+            equity_leg,
+            option_legs,
+            tag,
+        })
+    }
+}
+
+impl Sealed for ComboOrder {}
+
+impl OrderRequest for ComboOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![
+            ("class".to_owned(), OrderClass::Combo.to_string()),
+            ("symbol".to_owned(), self.symbol.clone()),
+            ("type".to_owned(), self.price_type.to_string()),
+            ("duration".to_owned(), self.duration.to_string()),
+        ];
+        if let Some(price) = self.price {
+            params.push(("price".to_owned(), format_price(price)));
+        }
+        params.push(("side[0]".to_owned(), self.equity_leg.side.to_string()));
+        params.push((
+            "quantity[0]".to_owned(),
+            self.equity_leg.quantity.to_string(),
+        ));
+        for (index, leg) in self.option_legs.iter().enumerate() {
+            let index = index + 1;
+            params.push((
+                format!("option_symbol[{index}]"),
+                leg.option_symbol.to_string(),
+            ));
+            params.push((format!("side[{index}]"), leg.side.to_string()));
+            params.push((format!("quantity[{index}]"), leg.quantity.to_string()));
+        }
+        if let Some(tag) = &self.tag {
+            params.push(("tag".to_owned(), tag.clone()));
+        }
+        params
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum LegInstrument {
+    Equity {
+        symbol: String,
+        side: OrderSide,
+    },
+    Option {
+        option_symbol: OptionSymbol,
+        side: OptionSide,
+    },
+}
+
+/// One leg of an advanced (OTO, OCO or OTOCO) order.
+///
+/// Each leg is a complete order on its own, with its own type, duration and prices. Legs are
+/// created with [`AdvancedLeg::equity`] or [`AdvancedLeg::option`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdvancedLeg {
+    instrument: LegInstrument,
+    quantity: u32,
+    order_type: OrderType,
+    duration: OrderDuration,
+    price: Option<f64>,
+    stop: Option<f64>,
+}
+
+#[bon::bon]
+impl AdvancedLeg {
+    /// Constructs an equity leg.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the symbol is blank, the quantity is zero, the duration
+    /// is not `day` or `gtc`, or the `price`/`stop` fields do not match the order type.
+    #[builder(
+        start_fn = equity,
+        finish_fn = build,
+        builder_type(name = AdvancedEquityLegBuilder, vis = "pub")
+    )]
+    fn new_equity(
+        #[builder(into)] symbol: String,
+        side: OrderSide,
+        quantity: u32,
+        order_type: OrderType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        stop: Option<f64>,
+    ) -> Result<Self> {
+        if symbol.trim().is_empty() {
+            return Err(Error::InvalidOrder("symbol must not be blank".to_owned()));
+        }
+        Self::validated(
+            LegInstrument::Equity { symbol, side },
+            quantity,
+            order_type,
+            duration,
+            price,
+            stop,
+        )
+    }
+
+    /// Constructs an option leg.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the quantity is zero, the duration is not `day` or
+    /// `gtc`, or the `price`/`stop` fields do not match the order type.
+    #[builder(
+        start_fn = option,
+        finish_fn = build,
+        builder_type(name = AdvancedOptionLegBuilder, vis = "pub")
+    )]
+    fn new_option(
+        option_symbol: OptionSymbol,
+        side: OptionSide,
+        quantity: u32,
+        order_type: OrderType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        stop: Option<f64>,
+    ) -> Result<Self> {
+        Self::validated(
+            LegInstrument::Option {
+                option_symbol,
+                side,
+            },
+            quantity,
+            order_type,
+            duration,
+            price,
+            stop,
+        )
+    }
+
+    fn validated(
+        instrument: LegInstrument,
+        quantity: u32,
+        order_type: OrderType,
+        duration: OrderDuration,
+        price: Option<f64>,
+        stop: Option<f64>,
+    ) -> Result<Self> {
+        validate_quantity(quantity)?;
+        validate_session_duration("advanced order legs", duration)?;
+        validate_pricing(order_type, price, stop)?;
+        Ok(AdvancedLeg {
+            instrument,
+            quantity,
+            order_type,
+            duration,
+            price,
+            stop,
+        })
+    }
+
+    /// The traded symbol, or the underlying root for option legs.
+    pub fn symbol(&self) -> &str {
+        match &self.instrument {
+            LegInstrument::Equity { symbol, .. } => symbol,
+            LegInstrument::Option { option_symbol, .. } => option_symbol.root(),
+        }
+    }
+
+    /// The option contract traded by this leg, if it is an option leg.
+    pub fn option_symbol(&self) -> Option<&OptionSymbol> {
+        match &self.instrument {
+            LegInstrument::Equity { .. } => None,
+            LegInstrument::Option { option_symbol, .. } => Some(option_symbol),
+        }
+    }
+
+    /// The pricing type of this leg.
+    pub fn order_type(&self) -> OrderType {
+        self.order_type
+    }
+
+    fn push_form_params(&self, index: usize, params: &mut Vec<(String, String)>) {
+        params.push((format!("symbol[{index}]"), self.symbol().to_owned()));
+        match &self.instrument {
+            LegInstrument::Equity { side, .. } => {
+                params.push((format!("side[{index}]"), side.to_string()));
+            }
+            LegInstrument::Option {
+                option_symbol,
+                side,
+            } => {
+                params.push((format!("option_symbol[{index}]"), option_symbol.to_string()));
+                params.push((format!("side[{index}]"), side.to_string()));
+            }
+        }
+        params.push((format!("quantity[{index}]"), self.quantity.to_string()));
+        params.push((format!("type[{index}]"), self.order_type.to_string()));
+        params.push((format!("duration[{index}]"), self.duration.to_string()));
+        if let Some(price) = self.price {
+            params.push((format!("price[{index}]"), format_price(price)));
+        }
+        if let Some(stop) = self.stop {
+            params.push((format!("stop[{index}]"), format_price(stop)));
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.option_symbol()
+            .map_or_else(|| self.symbol().to_owned(), ToString::to_string)
+    }
+}
+
+/// Checks that two legs that exit the same position trade the same symbol. Legs on different
+/// symbols would leave a one-cancels-other pair or a bracket guarding an unrelated position.
+fn validate_same_symbol(kind: &str, first: &AdvancedLeg, second: &AdvancedLeg) -> Result<()> {
+    if first.symbol() != second.symbol() {
+        return Err(Error::InvalidOrder(format!(
+            "{kind} legs must trade the same symbol, got {} and {}",
+            first.describe(),
+            second.describe()
+        )));
+    }
+    Ok(())
+}
+
+fn advanced_form_params(
+    class: OrderClass,
+    legs: &[AdvancedLeg],
+    tag: Option<&String>,
+) -> Vec<(String, String)> {
+    let mut params = vec![("class".to_owned(), class.to_string())];
+    for (index, leg) in legs.iter().enumerate() {
+        leg.push_form_params(index, &mut params);
+    }
+    if let Some(tag) = tag {
+        params.push(("tag".to_owned(), tag.clone()));
+    }
+    params
+}
+
+/// A one-triggers-other order (`class=oto`): the second leg is only sent once the first one
+/// fills.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtoOrder {
+    legs: [AdvancedLeg; 2],
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl OtoOrder {
+    /// Constructs a new `OtoOrder` from the triggering leg followed by the triggered leg. The
+    /// triggered leg may be any order, e.g. a hedge in another symbol or size.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(legs: [AdvancedLeg; 2], #[builder(into)] tag: Option<String>) -> Result<Self> {
+        Ok(OtoOrder { legs, tag })
+    }
+}
+
+impl Sealed for OtoOrder {}
+
+impl OrderRequest for OtoOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        advanced_form_params(OrderClass::Oto, &self.legs, self.tag.as_ref())
+    }
+}
+
+/// A one-cancels-other order (`class=oco`): when either leg fills, the other is canceled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcoOrder {
+    legs: [AdvancedLeg; 2],
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl OcoOrder {
+    /// Constructs a new `OcoOrder`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the legs trade different symbols.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(legs: [AdvancedLeg; 2], #[builder(into)] tag: Option<String>) -> Result<Self> {
+        validate_same_symbol("one-cancels-other", &legs[0], &legs[1])?;
+        Ok(OcoOrder { legs, tag })
+    }
+}
+
+impl Sealed for OcoOrder {}
+
+impl OrderRequest for OcoOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        advanced_form_params(OrderClass::Oco, &self.legs, self.tag.as_ref())
+    }
+}
+
+/// A one-triggers-one-cancels-other order (`class=otoco`), also known as a bracket: once the
+/// first leg fills, the remaining two legs are sent as a one-cancels-other pair.
+///
+/// # Example
+/// ```
+/// use tradier::types::{AdvancedLeg, OrderDuration, OrderSide, OrderType, OtocoOrder};
+///
+/// let entry = AdvancedLeg::equity()
+///     .symbol("AAPL")
+///     .side(OrderSide::Buy)
+///     .quantity(10)
+///     .order_type(OrderType::Limit)
+///     .duration(OrderDuration::Day)
+///     .price(150.0)
+///     .build()?;
+/// let take_profit = AdvancedLeg::equity()
+///     .symbol("AAPL")
+///     .side(OrderSide::Sell)
+///     .quantity(10)
+///     .order_type(OrderType::Limit)
+///     .duration(OrderDuration::Gtc)
+///     .price(165.0)
+///     .build()?;
+/// let stop_loss = AdvancedLeg::equity()
+///     .symbol("AAPL")
+///     .side(OrderSide::Sell)
+///     .quantity(10)
+///     .order_type(OrderType::Stop)
+///     .duration(OrderDuration::Gtc)
+///     .stop(140.0)
+///     .build()?;
+///
+/// let bracket = OtocoOrder::builder()
+///     .legs([entry, take_profit, stop_loss])
+///     .build()?;
+/// # let _ = bracket;
+/// # Ok::<(), tradier::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtocoOrder {
+    legs: [AdvancedLeg; 3],
+    tag: Option<String>,
+}
+
+#[bon::bon]
+impl OtocoOrder {
+    /// Constructs a new `OtocoOrder` from the triggering leg followed by the one-cancels-other
+    /// pair.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if the one-cancels-other legs do not trade the same
+    /// symbol as the first leg.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(legs: [AdvancedLeg; 3], #[builder(into)] tag: Option<String>) -> Result<Self> {
+        validate_same_symbol("bracket", &legs[0], &legs[1])?;
+        validate_same_symbol("bracket", &legs[0], &legs[2])?;
+        Ok(OtocoOrder { legs, tag })
+    }
+}
+
+impl Sealed for OtocoOrder {}
+
+impl OrderRequest for OtocoOrder {
+    fn form_params(&self) -> Vec<(String, String)> {
+        advanced_form_params(OrderClass::Otoco, &self.legs, self.tag.as_ref())
+    }
+}
+
+/// Changes to apply to an open order through
+/// [`modify_order`](crate::non_blocking::operation::Trading::modify_order).
+///
+/// Only the fields that are set are sent; Tradier keeps the current value of the others.
+///
+/// # Example
+/// ```
+/// use tradier::types::{OrderModification, OrderType};
+///
+/// let modification = OrderModification::builder()
+///     .order_type(OrderType::Limit)
+///     .price(151.5)
+///     .build()?;
+/// # let _ = modification;
+/// # Ok::<(), tradier::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderModification {
+    order_type: Option<OrderType>,
+    duration: Option<OrderDuration>,
+    price: Option<f64>,
+    stop: Option<f64>,
+}
+
+#[bon::bon]
+impl OrderModification {
+    /// Constructs a new `OrderModification`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidOrder`] if no field is set, a price or stop is not a positive
+    /// number with at most two decimals (four below $1), or, when the order type is changed, the
+    /// `price`/`stop` fields do not match it.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        order_type: Option<OrderType>,
+        duration: Option<OrderDuration>,
+        price: Option<f64>,
+        stop: Option<f64>,
+    ) -> Result<Self> {
+        if order_type.is_none() && duration.is_none() && price.is_none() && stop.is_none() {
+            return Err(Error::InvalidOrder(
+                "an order modification must change at least one field".to_owned(),
+            ));
+        }
+        match order_type {
+            Some(order_type) => validate_pricing(order_type, price, stop)?,
+            None => {
+                validate_price("price", price)?;
+                validate_price("stop", stop)?;
+            }
+        }
+        Ok(OrderModification {
+            order_type,
+            duration,
+            price,
+            stop,
+        })
+    }
+
+    pub(crate) fn form_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(order_type) = self.order_type {
+            params.push(("type".to_owned(), order_type.to_string()));
+        }
+        if let Some(duration) = self.duration {
+            params.push(("duration".to_owned(), duration.to_string()));
+        }
+        if let Some(price) = self.price {
+            params.push(("price".to_owned(), format_price(price)));
+        }
+        if let Some(stop) = self.stop {
+            params.push(("stop".to_owned(), format_price(stop)));
+        }
+        params
+    }
+}
+
+/// The response returned by Tradier after an order has been accepted.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct OrderResponse {
+    pub id: u64,
+    pub status: String,
+    pub partner_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub(crate) struct OrderResponseEnvelope {
+    pub(crate) order: OrderResponse,
+}
+
+/// The estimated impact of an order, returned by Tradier when an order is submitted with
+/// `preview=true`. Nothing is sent to the market.
+///
+/// Monetary fields are optional because Tradier omits them for some order classes.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct OrderPreview {
+    pub status: String,
+    /// Whether the order would be accepted as submitted.
+    #[serde(default)]
+    pub result: bool,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub commission: Option<f64>,
+    /// Total cost of the order, including commission and fees.
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub cost: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub fees: Option<f64>,
+    /// Cost of the order before commission and fees.
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub order_cost: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub margin_change: Option<f64>,
+    /// The order class as reported by Tradier, e.g. `equity` or `multileg`. Kept as a string so
+    /// classes this crate does not model do not fail the preview.
+    #[serde(default)]
+    pub class: Option<String>,
+    #[serde(default)]
+    pub strategy: Option<String>,
+    #[serde(default)]
+    pub day_trades: Option<u32>,
+    #[serde(default)]
+    pub extended_hours: Option<bool>,
+    #[serde(default, alias = "warning", deserialize_with = "vec_from_one_or_many")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub(crate) struct OrderPreviewEnvelope {
+    pub(crate) order: OrderPreview,
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::trading::test_support::OrderResponseEnvelopeWire;
+
+    fn param<'a>(params: &'a [(String, String)], key: &str) -> Option<&'a str> {
+        params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_limit_order_form_params() {
+        let order = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::Buy)
+            .quantity(10)
+            .order_type(OrderType::Limit)
+            .duration(OrderDuration::Gtc)
+            .price(150.25)
+            .tag("my-tag")
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("equity"));
+        assert_eq!(param(&params, "symbol"), Some("AAPL"));
+        assert_eq!(param(&params, "side"), Some("buy"));
+        assert_eq!(param(&params, "quantity"), Some("10"));
+        assert_eq!(param(&params, "type"), Some("limit"));
+        assert_eq!(param(&params, "duration"), Some("gtc"));
+        assert_eq!(param(&params, "price"), Some("150.25"));
+        assert_eq!(param(&params, "stop"), None);
+        assert_eq!(param(&params, "tag"), Some("my-tag"));
+    }
+
+    #[test]
+    fn test_prices_are_sent_with_fixed_decimals() {
+        assert_eq!(format_price(0.1 + 0.2), "0.3000");
+        assert_eq!(format_price(0.05), "0.0500");
+        assert_eq!(format_price(1.0), "1.00");
+
+        let order = OptionOrder::builder()
+            .option_symbol("SPY240119C00450000".parse().unwrap())
+            .side(OptionSide::BuyToOpen)
+            .quantity(1)
+            .order_type(OrderType::Limit)
+            .duration(OrderDuration::Day)
+            .price(0.1 + 0.2)
+            .build()
+            .unwrap();
+        assert_eq!(param(&order.form_params(), "price"), Some("0.3000"));
+    }
+
+    #[test]
+    fn test_sub_tick_prices_are_rejected() {
+        let limit = |price: f64| {
+            EquityOrder::builder()
+                .symbol("AAPL")
+                .side(OrderSide::Buy)
+                .quantity(1)
+                .order_type(OrderType::Limit)
+                .duration(OrderDuration::Day)
+                .price(price)
+                .build()
+        };
+        assert!(matches!(limit(150.255), Err(Error::InvalidOrder(_))));
+        assert!(matches!(limit(0.123_45), Err(Error::InvalidOrder(_))));
+        assert!(limit(150.25).is_ok());
+        assert!(limit(0.1234).is_ok());
+
+        let modification = OrderModification::builder().stop(9.999).build();
+        assert!(matches!(modification, Err(Error::InvalidOrder(_))));
+        assert!(matches!(
+            validate_net_pricing(NetPriceType::Credit, Some(1.005)),
+            Err(Error::InvalidOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_stop_limit_order_requires_price_and_stop() {
+        let missing_stop = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::SellShort)
+            .quantity(1)
+            .order_type(OrderType::StopLimit)
+            .duration(OrderDuration::Day)
+            .price(10.0)
+            .build();
+        assert!(matches!(missing_stop, Err(Error::InvalidOrder(_))));
+
+        let order = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::SellShort)
+            .quantity(1)
+            .order_type(OrderType::StopLimit)
+            .duration(OrderDuration::Day)
+            .price(10.0)
+            .stop(10.5)
+            .build()
+            .unwrap();
+        let params = order.form_params();
+        assert_eq!(param(&params, "type"), Some("stop_limit"));
+        assert_eq!(param(&params, "side"), Some("sell_short"));
+        assert_eq!(param(&params, "stop"), Some("10.50"));
+    }
+
+    #[test]
+    fn test_market_order_rejects_price() {
+        let order = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::Buy)
+            .quantity(1)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Day)
+            .price(10.0)
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_stop_order_rejects_non_positive_stop() {
+        let order = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::Sell)
+            .quantity(1)
+            .order_type(OrderType::Stop)
+            .duration(OrderDuration::Day)
+            .stop(-1.0)
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_order_rejects_blank_symbol_and_zero_quantity() {
+        let blank_symbol = EquityOrder::builder()
+            .symbol("  ")
+            .side(OrderSide::Buy)
+            .quantity(1)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Day)
+            .build();
+        assert!(blank_symbol.is_err());
+
+        let zero_quantity = EquityOrder::builder()
+            .symbol("AAPL")
+            .side(OrderSide::Buy)
+            .quantity(0)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Pre)
+            .build();
+        assert!(zero_quantity.is_err());
+    }
+
+    #[test]
+    fn test_display_values() {
+        assert_eq!(OrderSide::BuyToCover.to_string(), "buy_to_cover");
+        assert_eq!(OrderType::Stop.to_string(), "stop");
+        assert_eq!(OrderDuration::Post.to_string(), "post");
+        assert_eq!(OrderClass::Equity.to_string(), "equity");
+        assert_eq!(OrderClass::Option.to_string(), "option");
+        assert_eq!(OptionSide::BuyToClose.to_string(), "buy_to_close");
+        assert_eq!(OptionType::Put.to_string(), "put");
+        assert_eq!(OrderClass::Multileg.to_string(), "multileg");
+        assert_eq!(NetPriceType::Even.to_string(), "even");
+        assert_eq!(OrderClass::Combo.to_string(), "combo");
+        assert_eq!(OrderClass::Oto.to_string(), "oto");
+        assert_eq!(OrderClass::Oco.to_string(), "oco");
+        assert_eq!(OrderClass::Otoco.to_string(), "otoco");
+    }
+
+    #[test]
+    fn test_option_symbol_parse_and_format_round_trip() {
+        let symbol: OptionSymbol = "SPY240119P00450500".parse().unwrap();
+        assert_eq!(symbol.root(), "SPY");
+        assert_eq!(
+            symbol.expiration(),
+            NaiveDate::from_ymd_opt(2024, 1, 19).unwrap()
+        );
+        assert_eq!(symbol.option_type(), OptionType::Put);
+        assert_eq!(symbol.strike(), 450.5);
+        assert_eq!(symbol.to_string(), "SPY240119P00450500");
+    }
+
+    #[test]
+    fn test_option_symbol_accepts_space_padded_root() {
+        let symbol: OptionSymbol = "SPY   240119C00450000".parse().unwrap();
+        assert_eq!(symbol.root(), "SPY");
+        assert_eq!(symbol.to_string(), "SPY240119C00450000");
+    }
+
+    #[test]
+    fn test_option_symbol_rejects_invalid_symbols() {
+        let invalid = [
+            "",
+            "AAPL",
+            "240119C00150000",
+            "TOOLONGROOT240119C00150000",
+            "AAPL241319C00150000",
+            "AAPL240132C00150000",
+            "AAPL240119X00150000",
+            "AAPL240119C0015000A",
+            "AAPL240119C00000000",
+            "AA-L240119C00150000",
+            "AAPL240119C00150000\u{e9}",
+        ];
+        for symbol in invalid {
+            assert!(
+                matches!(
+                    symbol.parse::<OptionSymbol>(),
+                    Err(Error::OptionSymbolParseError(_))
+                ),
+                "expected {symbol:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_option_symbol_new() {
+        let expiration = NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
+        let symbol = OptionSymbol::new("AAPL", expiration, OptionType::Call, 187.5).unwrap();
+        assert_eq!(symbol.to_string(), "AAPL250620C00187500");
+        assert!(OptionSymbol::new("AAPL", expiration, OptionType::Call, 0.0).is_err());
+        assert!(OptionSymbol::new("", expiration, OptionType::Call, 1.0).is_err());
+        assert!(OptionSymbol::new("AAPL", expiration, OptionType::Call, 100_000.0).is_err());
+    }
+
+    #[test]
+    fn test_option_symbol_serde() {
+        let symbol: OptionSymbol = serde_json::from_str("\"AAPL240119C00150000\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&symbol).unwrap(),
+            "\"AAPL240119C00150000\""
+        );
+        assert!(serde_json::from_str::<OptionSymbol>("\"AAPL\"").is_err());
+    }
+
+    #[test]
+    fn test_option_order_form_params() {
+        let order = OptionOrder::builder()
+            .option_symbol("AAPL240119C00150000".parse().unwrap())
+            .side(OptionSide::SellToClose)
+            .quantity(2)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Gtc)
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("option"));
+        assert_eq!(param(&params, "symbol"), Some("AAPL"));
+        assert_eq!(param(&params, "option_symbol"), Some("AAPL240119C00150000"));
+        assert_eq!(param(&params, "side"), Some("sell_to_close"));
+        assert_eq!(param(&params, "quantity"), Some("2"));
+        assert_eq!(param(&params, "type"), Some("market"));
+        assert_eq!(param(&params, "duration"), Some("gtc"));
+        assert_eq!(param(&params, "price"), None);
+    }
+
+    #[test]
+    fn test_option_order_rejects_extended_hours_duration() {
+        let order = OptionOrder::builder()
+            .option_symbol("AAPL240119C00150000".parse().unwrap())
+            .side(OptionSide::BuyToOpen)
+            .quantity(1)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Pre)
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    fn leg(symbol: &str, side: OptionSide) -> OptionLeg {
+        OptionLeg::new(symbol.parse().unwrap(), side, 1)
+    }
+
+    #[test]
+    fn test_multileg_iron_condor_form_params() {
+        let order = MultilegOrder::builder()
+            .symbol("SPY")
+            .price_type(NetPriceType::Credit)
+            .price(1.1)
+            .duration(OrderDuration::Gtc)
+            .legs(vec![
+                leg("SPY240119P00440000", OptionSide::BuyToOpen),
+                leg("SPY240119P00445000", OptionSide::SellToOpen),
+                leg("SPY240119C00460000", OptionSide::SellToOpen),
+                leg("SPY240119C00465000", OptionSide::BuyToOpen),
+            ])
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("multileg"));
+        assert_eq!(param(&params, "symbol"), Some("SPY"));
+        assert_eq!(param(&params, "type"), Some("credit"));
+        assert_eq!(param(&params, "price"), Some("1.10"));
+        assert_eq!(param(&params, "duration"), Some("gtc"));
+        assert_eq!(
+            param(&params, "option_symbol[0]"),
+            Some("SPY240119P00440000")
+        );
+        assert_eq!(param(&params, "side[1]"), Some("sell_to_open"));
+        assert_eq!(
+            param(&params, "option_symbol[3]"),
+            Some("SPY240119C00465000")
+        );
+        assert_eq!(param(&params, "quantity[3]"), Some("1"));
+        assert_eq!(param(&params, "option_symbol[4]"), None);
+    }
+
+    #[test]
+    fn test_multileg_enforces_leg_count() {
+        let build = |legs: Vec<OptionLeg>| {
+            MultilegOrder::builder()
+                .symbol("SPY")
+                .price_type(NetPriceType::Even)
+                .duration(OrderDuration::Day)
+                .legs(legs)
+                .build()
+        };
+        assert!(build(vec![leg("SPY240119C00450000", OptionSide::BuyToOpen)]).is_err());
+        let five = (0..5)
+            .map(|i| leg(&format!("SPY240119C0045{i}000"), OptionSide::BuyToOpen))
+            .collect();
+        assert!(build(five).is_err());
+    }
+
+    #[test]
+    fn test_multileg_requires_distinct_legs() {
+        let repeated = MultilegOrder::builder()
+            .symbol("SPY")
+            .price_type(NetPriceType::Market)
+            .duration(OrderDuration::Day)
+            .legs(vec![
+                leg("SPY240119C00450000", OptionSide::BuyToOpen),
+                leg("SPY240119C00450000", OptionSide::SellToOpen),
+            ])
+            .build();
+        assert!(matches!(repeated, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_multileg_price_must_match_price_type() {
+        let legs = vec![
+            leg("SPY240119C00450000", OptionSide::BuyToOpen),
+            leg("SPY240119C00455000", OptionSide::SellToOpen),
+        ];
+        let build = |price_type: NetPriceType, price: Option<f64>| {
+            MultilegOrder::builder()
+                .symbol("SPY")
+                .price_type(price_type)
+                .maybe_price(price)
+                .duration(OrderDuration::Day)
+                .legs(legs.clone())
+                .build()
+        };
+        assert!(build(NetPriceType::Debit, Some(1.0)).is_ok());
+        assert!(build(NetPriceType::Debit, None).is_err());
+        assert!(build(NetPriceType::Credit, Some(-1.0)).is_err());
+        assert!(build(NetPriceType::Even, None).is_ok());
+        assert!(build(NetPriceType::Even, Some(1.0)).is_err());
+        assert!(build(NetPriceType::Market, Some(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_combo_collar_form_params() {
+        let order = ComboOrder::builder()
+            .symbol("AAPL")
+            .price_type(NetPriceType::Debit)
+            .price(150.0)
+            .duration(OrderDuration::Day)
+            .legs(vec![
+                leg("AAPL240119P00140000", OptionSide::BuyToOpen).into(),
+                EquityLeg::new(OrderSide::Buy, 100).into(),
+                leg("AAPL240119C00160000", OptionSide::SellToOpen).into(),
+            ])
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("combo"));
+        assert_eq!(param(&params, "symbol"), Some("AAPL"));
+        assert_eq!(param(&params, "type"), Some("debit"));
+        assert_eq!(param(&params, "side[0]"), Some("buy"));
+        assert_eq!(param(&params, "quantity[0]"), Some("100"));
+        assert_eq!(param(&params, "option_symbol[0]"), None);
+        assert_eq!(
+            param(&params, "option_symbol[1]"),
+            Some("AAPL240119P00140000")
+        );
+        assert_eq!(param(&params, "side[2]"), Some("sell_to_open"));
+    }
+
+    #[test]
+    fn test_combo_requires_exactly_one_equity_leg() {
+        let build = |legs: Vec<ComboLeg>| {
+            ComboOrder::builder()
+                .symbol("AAPL")
+                .price_type(NetPriceType::Market)
+                .duration(OrderDuration::Day)
+                .legs(legs)
+                .build()
+        };
+        let no_equity = build(vec![
+            leg("AAPL240119C00160000", OptionSide::SellToOpen).into()
+        ]);
+        assert!(matches!(no_equity, Err(Error::InvalidOrder(_))));
+
+        let two_equity = build(vec![
+            EquityLeg::new(OrderSide::Buy, 100).into(),
+            EquityLeg::new(OrderSide::Buy, 100).into(),
+            leg("AAPL240119C00160000", OptionSide::SellToOpen).into(),
+        ]);
+        assert!(matches!(two_equity, Err(Error::InvalidOrder(_))));
+
+        let no_options = build(vec![EquityLeg::new(OrderSide::Buy, 100).into()]);
+        assert!(matches!(no_options, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_multileg_accepts_roots_that_differ_from_the_underlying() {
+        let order = MultilegOrder::builder()
+            .symbol("SPX")
+            .price_type(NetPriceType::Credit)
+            .price(1.5)
+            .duration(OrderDuration::Day)
+            .legs(vec![
+                leg("SPXW240119P04700000", OptionSide::SellToOpen),
+                leg("SPXW240119P04690000", OptionSide::BuyToOpen),
+            ])
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "symbol"), Some("SPX"));
+        assert_eq!(
+            param(&params, "option_symbol[0]"),
+            Some("SPXW240119P04700000")
+        );
+    }
+
+    #[test]
+    fn test_combo_accepts_adjusted_option_roots() {
+        let order = ComboOrder::builder()
+            .symbol("AAPL")
+            .price_type(NetPriceType::Market)
+            .duration(OrderDuration::Day)
+            .legs(vec![
+                EquityLeg::new(OrderSide::Buy, 100).into(),
+                leg("AAPL1240119C00400000", OptionSide::SellToOpen).into(),
+            ])
+            .build();
+        assert!(order.is_ok());
+    }
+
+    fn equity_leg(symbol: &str, side: OrderSide, order_type: OrderType) -> AdvancedLeg {
+        let (price, stop) = match order_type {
+            OrderType::Market => (None, None),
+            OrderType::Limit => (Some(10.0), None),
+            OrderType::Stop => (None, Some(9.0)),
+            _ => (Some(10.0), Some(9.0)),
+        };
+        AdvancedLeg::equity()
+            .symbol(symbol)
+            .side(side)
+            .quantity(10)
+            .order_type(order_type)
+            .duration(OrderDuration::Gtc)
+            .maybe_price(price)
+            .maybe_stop(stop)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_otoco_form_params() {
+        let order = OtocoOrder::builder()
+            .legs([
+                equity_leg("AAPL", OrderSide::Buy, OrderType::Market),
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Limit),
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Stop),
+            ])
+            .tag("bracket")
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("otoco"));
+        assert_eq!(param(&params, "symbol[0]"), Some("AAPL"));
+        assert_eq!(param(&params, "type[0]"), Some("market"));
+        assert_eq!(param(&params, "price[0]"), None);
+        assert_eq!(param(&params, "side[1]"), Some("sell"));
+        assert_eq!(param(&params, "price[1]"), Some("10.00"));
+        assert_eq!(param(&params, "duration[1]"), Some("gtc"));
+        assert_eq!(param(&params, "type[2]"), Some("stop"));
+        assert_eq!(param(&params, "stop[2]"), Some("9.00"));
+        assert_eq!(param(&params, "quantity[2]"), Some("10"));
+        assert_eq!(param(&params, "tag"), Some("bracket"));
+    }
+
+    fn option_leg(side: OptionSide) -> AdvancedLeg {
+        AdvancedLeg::option()
+            .option_symbol("AAPL240119C00155000".parse().unwrap())
+            .side(side)
+            .quantity(1)
+            .order_type(OrderType::Limit)
+            .duration(OrderDuration::Day)
+            .price(2.5)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_oto_with_option_legs_form_params() {
+        let order = OtoOrder::builder()
+            .legs([
+                option_leg(OptionSide::BuyToOpen),
+                option_leg(OptionSide::SellToClose),
+            ])
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "class"), Some("oto"));
+        assert_eq!(param(&params, "symbol[0]"), Some("AAPL"));
+        assert_eq!(
+            param(&params, "option_symbol[1]"),
+            Some("AAPL240119C00155000")
+        );
+        assert_eq!(param(&params, "side[0]"), Some("buy_to_open"));
+        assert_eq!(param(&params, "side[1]"), Some("sell_to_close"));
+    }
+
+    #[test]
+    fn test_oto_accepts_any_triggered_leg() {
+        let mut hedge = equity_leg("SPY", OrderSide::SellShort, OrderType::Stop);
+        hedge.quantity = 5;
+        let order = OtoOrder::builder()
+            .legs([equity_leg("AAPL", OrderSide::Buy, OrderType::Limit), hedge])
+            .build()
+            .unwrap();
+
+        let params = order.form_params();
+        assert_eq!(param(&params, "symbol[1]"), Some("SPY"));
+        assert_eq!(param(&params, "side[1]"), Some("sell_short"));
+        assert_eq!(param(&params, "quantity[1]"), Some("5"));
+    }
+
+    #[test]
+    fn test_oco_accepts_legs_on_either_side_in_any_size() {
+        let mut breakdown = equity_leg("AAPL", OrderSide::SellShort, OrderType::Market);
+        breakdown.quantity = 5;
+        let order = OcoOrder::builder()
+            .legs([
+                equity_leg("AAPL", OrderSide::Buy, OrderType::Stop),
+                breakdown,
+            ])
+            .build();
+        assert!(order.is_ok());
+    }
+
+    #[test]
+    fn test_oco_rejects_legs_on_different_symbols() {
+        let order = OcoOrder::builder()
+            .legs([
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Limit),
+                equity_leg("MSFT", OrderSide::Sell, OrderType::Stop),
+            ])
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_otoco_validates_its_oco_pair() {
+        let order = OtocoOrder::builder()
+            .legs([
+                equity_leg("AAPL", OrderSide::Buy, OrderType::Limit),
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Limit),
+                equity_leg("MSFT", OrderSide::Sell, OrderType::Stop),
+            ])
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_otoco_brackets_must_trade_the_entry_symbol() {
+        let order = OtocoOrder::builder()
+            .legs([
+                equity_leg("MSFT", OrderSide::Buy, OrderType::Limit),
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Limit),
+                equity_leg("AAPL", OrderSide::Sell, OrderType::Stop),
+            ])
+            .build();
+        assert!(matches!(order, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_advanced_leg_validation() {
+        let extended_hours = AdvancedLeg::equity()
+            .symbol("AAPL")
+            .side(OrderSide::Buy)
+            .quantity(1)
+            .order_type(OrderType::Market)
+            .duration(OrderDuration::Post)
+            .build();
+        assert!(matches!(extended_hours, Err(Error::InvalidOrder(_))));
+
+        let missing_price = AdvancedLeg::equity()
+            .symbol("AAPL")
+            .side(OrderSide::Buy)
+            .quantity(1)
+            .order_type(OrderType::Limit)
+            .duration(OrderDuration::Day)
+            .build();
+        assert!(matches!(missing_price, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn test_order_preview_deserialization() {
+        let json = r#"{"order":{"status":"ok","commission":"1.00","cost":1701.0,"fees":0,"symbol":"SPY","quantity":1,"side":"buy","type":"market","duration":"day","result":true,"order_cost":1700.0,"margin_change":0.0,"request_date":"2019-03-29T14:34:55.000Z","extended_hours":false,"class":"equity","strategy":"equity","day_trades":0,"warning":"Order may be subject to a wash sale"}}"#;
+        let preview = serde_json::from_str::<OrderPreviewEnvelope>(json)
+            .unwrap()
+            .order;
+        assert!(preview.result);
+        assert_eq!(preview.commission, Some(1.0));
+        assert_eq!(preview.cost, Some(1701.0));
+        assert_eq!(preview.order_cost, Some(1700.0));
+        assert_eq!(preview.margin_change, Some(0.0));
+        assert_eq!(preview.class.as_deref(), Some("equity"));
+        assert_eq!(
+            preview.warnings,
+            vec!["Order may be subject to a wash sale"]
+        );
+    }
+
+    #[test]
+    fn test_order_preview_accepts_unknown_classes() {
+        let json =
+            r#"{"order":{"status":"ok","result":true,"class":"spread","strategy":"spread"}}"#;
+        let preview = serde_json::from_str::<OrderPreviewEnvelope>(json)
+            .unwrap()
+            .order;
+        assert_eq!(preview.class.as_deref(), Some("spread"));
+        assert_eq!(preview.cost, None);
+    }
+
+    #[test]
+    fn test_order_modification_form_params() {
+        let modification = OrderModification::builder()
+            .order_type(OrderType::StopLimit)
+            .duration(OrderDuration::Gtc)
+            .price(10.5)
+            .stop(10.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            modification.form_params(),
+            vec![
+                ("type".to_owned(), "stop_limit".to_owned()),
+                ("duration".to_owned(), "gtc".to_owned()),
+                ("price".to_owned(), "10.50".to_owned()),
+                ("stop".to_owned(), "10.00".to_owned()),
+            ]
+        );
+
+        let price_only = OrderModification::builder().price(11.0).build().unwrap();
+        assert_eq!(
+            price_only.form_params(),
+            vec![("price".to_owned(), "11.00".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_order_modification_validation() {
+        assert!(matches!(
+            OrderModification::builder().build(),
+            Err(Error::InvalidOrder(_))
+        ));
+        assert!(matches!(
+            OrderModification::builder()
+                .order_type(OrderType::Market)
+                .price(10.0)
+                .build(),
+            Err(Error::InvalidOrder(_))
+        ));
+        assert!(matches!(
+            OrderModification::builder().stop(-1.0).build(),
+            Err(Error::InvalidOrder(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn test_option_symbol_round_trips(
+            root in "[A-Z]{1,6}",
+            days in 0i64..36_000,
+            is_call in any::<bool>(),
+            strike in 1u32..100_000_000,
+        ) {
+            let expiration = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap() + chrono::Duration::days(days);
+            prop_assume!(expiration.year() <= 2099);
+            let option_type = if is_call { OptionType::Call } else { OptionType::Put };
+            let symbol = OptionSymbol::new(&root, expiration, option_type, f64::from(strike) / 1000.0)
+                .expect("valid components");
+            let parsed: OptionSymbol = symbol.to_string().parse().expect("formatted symbol to parse");
+            prop_assert_eq!(parsed, symbol);
+        }
+
+        #[test]
+        fn test_deserialize_order_response_from_json(response in any::<OrderResponseEnvelopeWire>()) {
+            let response = serde_json::to_string_pretty(&response)
+                .expect("test fixture to serialize");
+            let result: std::result::Result<OrderResponseEnvelope, serde_json::Error> = serde_json::from_str(&response);
+            assert!(result.is_ok());
+        }
+    }
+}
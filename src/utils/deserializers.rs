@@ -0,0 +1,239 @@
+//! Lenient field deserializers for Tradier payloads.
+//!
+//! Tradier is inconsistent about how it encodes scalar values: the same field may arrive as a
+//! JSON number in one payload and as a quoted string in another (e.g. `"price": "281.1"` on a
+//! streaming trade event). Timestamps are typically milliseconds since the Unix epoch, again
+//! either quoted or not. The helpers in this module accept both forms.
+//!
+//! Collections are just as inconsistent: a list may arrive as an array, as a single bare value,
+//! or wrapped in an envelope that reads `"null"` when empty. [`vec_from_one_or_many`] and
+//! [`envelope`] accept each of these shapes, while [`envelope`] still checks that the envelope
+//! is keyed on the expected item name.
+use chrono::{DateTime, Utc};
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::utils::OneOrMany;
+
+fn value_to_f64<E: serde::de::Error>(value: Value) -> Result<Option<f64>, E> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Number(n) => n
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| E::custom(format!("{n} is not representable as f64"))),
+        Value::String(s) if s.trim().is_empty() => Ok(None),
+        Value::String(s) => s.trim().parse::<f64>().map(Some).map_err(E::custom),
+        other => Err(E::custom(format!("expected a number, found {other}"))),
+    }
+}
+
+fn value_to_u64<E: serde::de::Error>(value: Value) -> Result<Option<u64>, E> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Number(n) => n
+            .as_u64()
+            .or_else(|| n.as_f64().filter(|f| *f >= 0.0).map(|f| f as u64))
+            .map(Some)
+            .ok_or_else(|| E::custom(format!("{n} is not an unsigned integer"))),
+        Value::String(s) if s.trim().is_empty() => Ok(None),
+        Value::String(s) => s.trim().parse::<u64>().map(Some).map_err(E::custom),
+        other => Err(E::custom(format!(
+            "expected an unsigned integer, found {other}"
+        ))),
+    }
+}
+
+fn millis_to_datetime<E: serde::de::Error>(
+    millis: Option<u64>,
+) -> Result<Option<DateTime<Utc>>, E> {
+    millis
+        .map(|millis| {
+            i64::try_from(millis)
+                .ok()
+                .and_then(DateTime::from_timestamp_millis)
+                .ok_or_else(|| E::custom(format!("{millis} is not a valid timestamp")))
+        })
+        .transpose()
+}
+
+/// Deserializes an `f64` that may be encoded as a number or a numeric string.
+pub(crate) fn f64_lenient<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    value_to_f64(Value::deserialize(deserializer)?)?
+        .ok_or_else(|| D::Error::custom("expected a number, found null"))
+}
+
+/// Like [`f64_lenient`], but maps `null` and empty strings to `None`.
+pub(crate) fn option_f64_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    value_to_f64(Value::deserialize(deserializer)?)
+}
+
+/// Deserializes a `u64` that may be encoded as a number or a numeric string.
+pub(crate) fn u64_lenient<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    value_to_u64(Value::deserialize(deserializer)?)?
+        .ok_or_else(|| D::Error::custom("expected an unsigned integer, found null"))
+}
+
+/// Like [`u64_lenient`], but maps `null` and empty strings to `None`.
+pub(crate) fn option_u64_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    value_to_u64(Value::deserialize(deserializer)?)
+}
+
+/// Deserializes a millisecond Unix timestamp, quoted or not, into a `DateTime<Utc>`.
+pub(crate) fn datetime_from_millis<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    millis_to_datetime(value_to_u64(Value::deserialize(deserializer)?)?)?
+        .ok_or_else(|| D::Error::custom("expected a timestamp, found null"))
+}
+
+/// Deserializes a field that may hold a single value, an array of values or `null` into a `Vec`.
+pub(crate) fn vec_from_one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<OneOrMany<T>>::deserialize(deserializer)?
+        .map(OneOrMany::into_vec)
+        .unwrap_or_default())
+}
+
+/// An item that Tradier returns inside a collection envelope.
+pub(crate) trait EnvelopeItem {
+    /// The key the envelope wraps its items in, e.g. `position` for `"positions"`.
+    const KEY: &'static str;
+}
+
+/// Deserializes a Tradier collection envelope such as `"positions": {"position": [...]}` into
+/// the items it wraps.
+///
+/// Tradier wraps every collection in an object holding a single key named after the item type,
+/// given by [`EnvelopeItem::KEY`]; an object keyed on anything else is rejected. The envelope
+/// itself is the string `"null"` (or `null`) when the collection is empty, and the wrapped value
+/// is a bare object when there is exactly one item. All of these forms, as well as a bare array
+/// in place of the envelope, are accepted.
+pub(crate) fn envelope<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned + EnvelopeItem,
+{
+    let wrapped = match Value::deserialize(deserializer)? {
+        Value::Null => return Ok(Vec::new()),
+        Value::String(s) if s == "null" => return Ok(Vec::new()),
+        Value::Object(map) if map.is_empty() => return Ok(Vec::new()),
+        Value::Object(mut map) if map.len() == 1 => match map.remove(T::KEY) {
+            Some(value) => value,
+            None => {
+                return Err(D::Error::custom(format!(
+                    "expected a collection envelope keyed on `{}`, found {}",
+                    T::KEY,
+                    Value::Object(map)
+                )))
+            }
+        },
+        array @ Value::Array(_) => array,
+        other => {
+            return Err(D::Error::custom(format!(
+                "expected a collection envelope, found {other}"
+            )))
+        }
+    };
+    Option::<OneOrMany<T>>::deserialize(wrapped)
+        .map(|items| items.map(OneOrMany::into_vec).unwrap_or_default())
+        .map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Fixture {
+        #[serde(deserialize_with = "f64_lenient")]
+        price: f64,
+        #[serde(default, deserialize_with = "option_f64_lenient")]
+        close: Option<f64>,
+        #[serde(deserialize_with = "u64_lenient")]
+        size: u64,
+        #[serde(deserialize_with = "datetime_from_millis")]
+        date: DateTime<Utc>,
+    }
+
+    #[test]
+    fn test_accepts_quoted_and_unquoted_values() {
+        let quoted: Fixture = serde_json::from_str(
+            r#"{"price": "281.1", "close": "", "size": "100", "date": "1557757189000"}"#,
+        )
+        .unwrap();
+        let unquoted: Fixture = serde_json::from_str(
+            r#"{"price": 281.1, "close": 280.5, "size": 100, "date": 1557757189000}"#,
+        )
+        .unwrap();
+
+        assert_eq!(quoted.price, unquoted.price);
+        assert_eq!(quoted.size, unquoted.size);
+        assert_eq!(quoted.date, unquoted.date);
+        assert_eq!(quoted.close, None);
+        assert_eq!(unquoted.close, Some(280.5));
+        assert_eq!(quoted.date.timestamp_millis(), 1557757189000);
+    }
+
+    #[test]
+    fn test_missing_optional_field_is_none() {
+        let fixture: Fixture =
+            serde_json::from_str(r#"{"price": 1, "size": 1, "date": 0}"#).unwrap();
+        assert_eq!(fixture.close, None);
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Warnings {
+        #[serde(default, deserialize_with = "vec_from_one_or_many")]
+        warnings: Vec<String>,
+    }
+
+    #[test]
+    fn test_vec_from_one_or_many() {
+        let parse = |json: &str| serde_json::from_str::<Warnings>(json).unwrap().warnings;
+        assert_eq!(parse(r#"{"warnings": "a"}"#), vec!["a"]);
+        assert_eq!(parse(r#"{"warnings": ["a", "b"]}"#), vec!["a", "b"]);
+        assert!(parse(r#"{"warnings": null}"#).is_empty());
+        assert!(parse("{}").is_empty());
+    }
+
+    impl EnvelopeItem for u32 {
+        const KEY: &'static str = "item";
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        #[serde(deserialize_with = "envelope")]
+        items: Vec<u32>,
+    }
+
+    #[test]
+    fn test_envelope_shapes() {
+        let parse = |json: &str| serde_json::from_str::<Wrapper>(json).unwrap().items;
+        assert!(parse(r#"{"items": "null"}"#).is_empty());
+        assert!(parse(r#"{"items": null}"#).is_empty());
+        assert!(parse(r#"{"items": {}}"#).is_empty());
+        assert!(parse(r#"{"items": {"item": null}}"#).is_empty());
+        assert_eq!(parse(r#"{"items": {"item": 1}}"#), vec![1]);
+        assert_eq!(parse(r#"{"items": {"item": [1, 2]}}"#), vec![1, 2]);
+        assert_eq!(parse(r#"{"items": [1, 2]}"#), vec![1, 2]);
+        assert!(serde_json::from_str::<Wrapper>(r#"{"items": "other"}"#).is_err());
+        assert!(serde_json::from_str::<Wrapper>(r#"{"items": {"a": 1, "b": 2}}"#).is_err());
+        assert!(serde_json::from_str::<Wrapper>(r#"{"items": {"other": [1, 2]}}"#).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_numeric_strings() {
+        let result = serde_json::from_str::<Fixture>(
+            r#"{"price": "abc", "size": "100", "date": "1557757189000"}"#,
+        );
+        assert!(result.is_err());
+    }
+}
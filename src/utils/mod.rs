@@ -1,9 +1,12 @@
+pub(crate) mod deserializers;
 pub mod logger;
 mod one_or_many;
+mod pager;
 mod sealed;
 #[cfg(test)]
 pub(crate) mod tests;
 
 pub use one_or_many::OneOrMany;
+pub use pager::{PageIter, PageStream};
 
 pub(crate) use sealed::Sealed;
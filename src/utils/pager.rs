@@ -0,0 +1,162 @@
+//! Automatic pagination for paginated Tradier endpoints.
+//!
+//! Endpoints such as account history and gain/loss return results one [`Page`] at a time. The
+//! pager requests pages in order, starting from a caller-chosen page, and stops at the first
+//! page that comes back empty. Up to `prefetch` pages may be in flight at once; pages are
+//! always yielded in order, so a prefetch above one only trades a few extra requests past the
+//! last page for lower latency.
+use std::future::{ready, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt};
+use tokio::runtime::Runtime;
+
+use crate::accounts::types::Page;
+use crate::Result;
+
+/// A stream of items from a paginated endpoint, fetched page by page as it is polled.
+///
+/// The stream ends after the first empty page. If a page fails to load, the error is yielded
+/// and the stream ends.
+pub struct PageStream<'a, T> {
+    inner: BoxStream<'a, Result<T>>,
+}
+
+impl<'a, T: Send + 'a> PageStream<'a, T> {
+    /// Builds a stream that calls `fetch` with consecutive pages, starting at `first_page`,
+    /// with at most `prefetch` requests in flight.
+    pub(crate) fn new<F, Fut>(first_page: Page, prefetch: usize, fetch: F) -> Self
+    where
+        F: Fn(Page) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Vec<T>>> + Send + 'a,
+    {
+        let items = stream::iter(first_page.number()..=i32::MAX)
+            .map(move |number| fetch(Page::new(number)))
+            .buffered(prefetch.max(1))
+            .scan(false, |failed, page| {
+                if *failed {
+                    return ready(None);
+                }
+                let items: Vec<Result<T>> = match page {
+                    Ok(items) if items.is_empty() => return ready(None),
+                    Ok(items) => items.into_iter().map(Ok).collect(),
+                    Err(e) => {
+                        *failed = true;
+                        vec![Err(e)]
+                    }
+                };
+                ready(Some(stream::iter(items)))
+            })
+            .flatten();
+        PageStream {
+            inner: items.boxed(),
+        }
+    }
+}
+
+impl<T> Stream for PageStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// A blocking iterator over a [`PageStream`], driven by the runtime of a
+/// [`BlockingTradierRestClient`](crate::blocking::Client).
+pub struct PageIter<'a, T> {
+    runtime: &'a Runtime,
+    pages: PageStream<'a, T>,
+}
+
+impl<'a, T> PageIter<'a, T> {
+    pub(crate) fn new(runtime: &'a Runtime, pages: PageStream<'a, T>) -> Self {
+        PageIter { runtime, pages }
+    }
+}
+
+impl<T> Iterator for PageIter<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.pages.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::Error;
+
+    fn pages_of(
+        total_pages: i32,
+        requests: Arc<AtomicUsize>,
+    ) -> impl Fn(Page) -> std::future::Ready<Result<Vec<i32>>> {
+        move |page| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let number = page.number();
+            ready(Ok(if number <= total_pages {
+                vec![number * 10, number * 10 + 1]
+            } else {
+                vec![]
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_walks_pages_until_an_empty_one() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let items: Vec<i32> = PageStream::new(Page::default(), 1, pages_of(3, requests.clone()))
+            .map(|item| item.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![10, 11, 20, 21, 30, 31]);
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_preserves_page_order() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let items: Vec<i32> = PageStream::new(Page::new(2), 4, pages_of(5, requests.clone()))
+            .map(|item| item.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![20, 21, 30, 31, 40, 41, 50, 51]);
+        assert!(requests.load(Ordering::SeqCst) <= 8);
+    }
+
+    #[tokio::test]
+    async fn test_stops_after_an_error() {
+        let pages = PageStream::new(Page::default(), 1, |page: Page| {
+            ready(match page.number() {
+                1 => Ok(vec![1]),
+                2 => Err(Error::UnexpectedError("boom".to_owned())),
+                _ => Ok(vec![3]),
+            })
+        });
+        let items: Vec<Result<i32>> = pages.collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_ok());
+        assert!(items[1].is_err());
+    }
+
+    #[test]
+    fn test_page_iter_blocks_on_the_stream() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let pages = PageStream::new(Page::default(), 2, pages_of(2, requests));
+        let items: Vec<i32> = PageIter::new(&runtime, pages)
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(items, vec![10, 11, 20, 21]);
+    }
+}
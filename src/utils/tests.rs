@@ -1,9 +1,6 @@
 use std::{env, sync::Mutex};
 
-use crate::{
-    config::{Config, Credentials, RestApiConfig, StreamingConfig},
-    wssession::MarketSessionPayload,
-};
+use crate::config::{Config, Credentials, RestApiConfig, StreamTransport, StreamingConfig};
 use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
 use proptest::prelude::Strategy;
@@ -54,6 +51,8 @@ pub(crate) fn create_test_config(
     server_url: &str,
     #[builder(default)] web_socket_url: &str,
     #[builder(default)] web_socket_path: &str,
+    #[builder(default)] http_base_url: &str,
+    #[builder(default)] transport: StreamTransport,
     #[builder(default)] is_sandbox: bool,
 ) -> Config {
     Config {
@@ -72,10 +71,11 @@ pub(crate) fn create_test_config(
             timeout: 30,
         },
         streaming: StreamingConfig {
-            http_base_url: "".to_string(),
+            http_base_url: http_base_url.to_string(),
             ws_base_url: web_socket_url.to_string(),
             events_path: web_socket_path.to_string(),
             reconnect_interval: 5,
+            transport,
         },
     }
 }
@@ -110,9 +110,9 @@ where
 
 #[bon::builder(finish_fn = create)]
 #[cfg(test)]
-pub(crate) async fn mock_websocket_server(
+pub(crate) async fn mock_websocket_server<P: Serialize>(
     #[builder(with = |a: &'static str, p: u16| (a, p) )] address: (&str, u16),
-    expected_request: MarketSessionPayload<'_>,
+    expected_request: P,
     expected_response: &'static str,
 ) {
     let expected_request = serde_json::to_string(&expected_request).expect("serialization to work");
@@ -152,3 +152,30 @@ fn arb_date_time_strategy() -> impl Strategy<Value = DateTime<Utc>> {
         |(seconds, nanos)| DateTime::from_timestamp(seconds, nanos),
     )
 }
+
+/// The forms Tradier uses to encode a collection envelope such as `positions.position`.
+#[derive(Clone, Copy, Debug, proptest_derive::Arbitrary)]
+pub(crate) enum EnvelopeShape {
+    /// `"null"` in place of the envelope, sent for empty collections.
+    NullString,
+    /// A bare object in place of the array, sent when there is exactly one item.
+    Single,
+    /// An array of items.
+    Many,
+}
+
+/// Wraps `items` in a Tradier collection envelope `{ key: ... }` of the given shape, returning
+/// the JSON value along with the number of items it holds.
+pub(crate) fn envelope_json<T: Serialize>(
+    key: &str,
+    items: &[T],
+    shape: EnvelopeShape,
+) -> (serde_json::Value, usize) {
+    match (shape, items.first()) {
+        (EnvelopeShape::NullString, _) | (EnvelopeShape::Single, None) => {
+            (serde_json::Value::from("null"), 0)
+        }
+        (EnvelopeShape::Single, Some(item)) => (serde_json::json!({ key: item }), 1),
+        (EnvelopeShape::Many, _) => (serde_json::json!({ key: items }), items.len()),
+    }
+}
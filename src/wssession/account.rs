@@ -12,7 +12,7 @@
 //!
 //! An `AccountSession` in this module wraps Tradier’s WebSocket session specifically for account-related events,
 //! ensuring efficient management of the WebSocket connection and enabling clients to receive streaming data
-//! continuously. The `SessionManager` is used to enforce a singleton pattern, allowing only one session at a time
+//! continuously. The `SessionManager` is used to enforce a singleton pattern, allowing only one account session at a time
 //! to prevent overlapping WebSocket connections.
 //!
 //! ## Usage
@@ -54,10 +54,25 @@
 //! The `AccountSession` creation will return an error in cases such as:
 //! - Missing or invalid API credentials
 //! - Network connectivity issues preventing WebSocket connection establishment
-//! - Attempting to create multiple sessions simultaneously, which is restricted by `SessionManager`
+//! - Attempting to create multiple account sessions simultaneously, which is restricted by `SessionManager`
 //!
 //! For additional details on the API, refer to the [Tradier Account WebSocket documentation](https://documentation.tradier.com/brokerage-api/streaming/wss-account-websocket).
 
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::BoxStream;
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::connect_async;
+use tracing::info;
+use tungstenite::Message;
+use url::Url;
+
+use crate::wssession::events::{parse_account_frame, websocket_events, OrderEvent};
+use crate::wssession::reconnect::{ReconnectPolicy, ReconnectingStream, Resubscribe, Subscription};
 use crate::wssession::session::{Session, SessionType};
 use crate::Config;
 use crate::Result;
@@ -66,6 +81,72 @@ use self::session_manager::{SessionManager, GLOBAL_SESSION_MANAGER};
 
 use super::session_manager;
 
+/// Payload for a Tradier Account WebSocket session, subscribing to the order events of every
+/// account of the user.
+///
+/// Fields:
+/// - `events`: The event types to subscribe to. Tradier only supports `order`.
+/// - `session_id`: Unique session identifier.
+/// - `exclude_accounts`: Optional account numbers whose events should not be delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountSessionPayload<'a> {
+    pub events: Cow<'a, [String]>,
+    #[serde(rename = "sessionid")]
+    pub session_id: Cow<'a, str>,
+    #[serde(rename = "excludeAccounts", skip_serializing_if = "Option::is_none")]
+    pub exclude_accounts: Option<Cow<'a, [String]>>,
+}
+
+#[bon::bon]
+impl<'a> AccountSessionPayload<'a> {
+    /// Constructs a new `AccountSessionPayload` subscribing to order events.
+    ///
+    /// # Arguments
+    /// - `session_id`: A unique session identifier for the WebSocket connection.
+    /// - `exclude_accounts`: Account numbers whose events should be filtered out by Tradier.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(session_id: &'a str, exclude_accounts: Option<&'a [String]>) -> Self {
+        AccountSessionPayload {
+            events: Cow::Owned(vec!["order".to_owned()]),
+            session_id: Cow::Borrowed(session_id),
+            exclude_accounts: exclude_accounts.map(Cow::Borrowed),
+        }
+    }
+
+    /// Copies the borrowed parts of the payload, so that it can be sent again by a
+    /// [`ReconnectingStream`].
+    pub fn into_owned(self) -> AccountSessionPayload<'static> {
+        AccountSessionPayload {
+            events: Cow::Owned(self.events.into_owned()),
+            session_id: Cow::Owned(self.session_id.into_owned()),
+            exclude_accounts: self
+                .exclude_accounts
+                .map(|accounts| Cow::Owned(accounts.into_owned())),
+        }
+    }
+
+    /// Converts the payload to a WebSocket `Message` for sending.
+    ///
+    /// # Returns
+    /// - `Ok(Message)`: The WebSocket message if serialization is successful.
+    /// - `Err(Error)`: An error if serialization fails.
+    pub fn get_message(&self) -> Result<Message> {
+        serde_json::to_string(self)
+            .map(|s| Message::Text(s.into()))
+            .map_err(Into::into)
+    }
+}
+
+impl Display for AccountSessionPayload<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AccountSessionPayload {{ events: {:?}, session_id: {}, exclude_accounts: {:?} }}",
+            self.events, self.session_id, self.exclude_accounts
+        )
+    }
+}
+
 /// `AccountSession` is a wrapper around a `Session` specifically for account-level WebSocket interactions.
 ///
 /// This struct is designed to interact with Tradier's Account WebSocket API, which provides real-time
@@ -73,8 +154,11 @@ use super::session_manager;
 /// encapsulates a `Session` initialized with `SessionType::Account`, offering convenient methods to
 /// establish and manage WebSocket connections for account-related data.
 ///
+/// Only one `AccountSession` can exist at a time; its slot is released when it is dropped, after
+/// which a new one can be created. A `MarketSession` may be open alongside it.
+///
 /// For more details on the Tradier Account WebSocket API, see the official [documentation](https://documentation.tradier.com/brokerage-api/streaming/wss-account-websocket).
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct AccountSession<'a>(Session<'a>);
 
 impl<'a> AccountSession<'a> {
@@ -178,14 +262,150 @@ impl<'a> AccountSession<'a> {
     pub fn get_websocket_url(&self) -> &str {
         self.0.get_websocket_url()
     }
+
+    /// Connects to the account events socket and streams typed order events.
+    ///
+    /// # Arguments
+    /// - `payload`: An `AccountSessionPayload` carrying the session id and the accounts to exclude.
+    ///
+    /// # Returns
+    /// - `Ok(AccountEventStream)`: A stream of [`OrderEvent`] values once the subscription was sent.
+    /// - `Err(Error)`: If the WebSocket connection or the subscription request fails.
+    ///
+    /// # Behavior
+    /// - Heartbeats are consumed silently.
+    /// - Payloads that cannot be parsed are yielded as [`Error::StreamEventParseError`] without
+    ///   terminating the stream.
+    /// - Terminates on connection close, or after yielding a [`Error::WebSocketError`].
+    ///
+    /// [`Error::StreamEventParseError`]: crate::Error::StreamEventParseError
+    /// [`Error::WebSocketError`]: crate::Error::WebSocketError
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use tradier::Config;
+    /// use tradier::wssession::{AccountSession, AccountSessionPayload};
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let config = Config::new();
+    ///     let account_session = AccountSession::new(&config)
+    ///         .await
+    ///         .expect("Failed to create account session");
+    ///     let payload = AccountSessionPayload::builder()
+    ///         .session_id(account_session.get_session_id())
+    ///         .build();
+    ///     let mut events = account_session.ws_stream(payload).await.unwrap();
+    ///     while let Some(Ok(event)) = events.next().await {
+    ///         println!("{} {:?}: {} filled", event.id, event.status, event.executed_quantity);
+    ///     }
+    /// }
+    /// ```
+    pub async fn ws_stream(
+        &self,
+        payload: AccountSessionPayload<'_>,
+    ) -> Result<AccountEventStream> {
+        let uri = self.get_websocket_url();
+        let url = Url::parse(uri)?;
+
+        info!("Connecting to: {}", uri);
+        let (ws_stream, _) = connect_async(url.as_str()).await.map_err(Box::new)?;
+        let (mut write, read) = ws_stream.split();
+
+        let message = payload.get_message()?;
+        write.send(message).await.map_err(Box::new)?;
+        info!("Sent payload: {}", payload);
+
+        Ok(AccountEventStream::from_websocket(read))
+    }
+
+    /// Streams typed order events, reconnecting whenever the connection is lost.
+    ///
+    /// Behaves like [`MarketSession::reconnecting_stream`](crate::wssession::MarketSession::reconnecting_stream):
+    /// the session is renewed once it expired or a reconnection attempt failed, and the
+    /// payload is sent again with the id of the current session.
+    pub async fn reconnecting_stream(
+        self,
+        config: &Config,
+        payload: AccountSessionPayload<'_>,
+        policy: ReconnectPolicy,
+    ) -> Result<ReconnectingStream<'a, OrderEvent>> {
+        let payload = payload.into_owned();
+        let events = self.ws_stream(payload.clone()).await?;
+        let subscription = Subscription {
+            session: self,
+            config: config.clone(),
+            payload,
+        };
+        Ok(ReconnectingStream::new(
+            subscription,
+            events.boxed(),
+            policy,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> Resubscribe for Subscription<AccountSession<'a>, AccountSessionPayload<'static>> {
+    type Event = OrderEvent;
+
+    async fn resubscribe(
+        &mut self,
+        renew_session: bool,
+    ) -> Result<(BoxStream<'static, Result<OrderEvent>>, bool)> {
+        let renew_session = renew_session || self.session.0.is_expired();
+        if renew_session {
+            self.session.0.renew(&self.config).await?;
+        }
+        self.payload.session_id = Cow::Owned(self.session.get_session_id().to_owned());
+        let events = self.session.ws_stream(self.payload.clone()).await?;
+        Ok((events.boxed(), renew_session))
+    }
+
+    fn disconnected(&mut self) {
+        self.session.0.mark_used();
+    }
+}
+
+/// A stream of typed [`OrderEvent`] values received from an account session.
+///
+/// Parse errors do not end the stream; it only ends when the connection is closed or a
+/// transport error occurs.
+pub struct AccountEventStream {
+    inner: BoxStream<'static, Result<OrderEvent>>,
+}
+
+impl AccountEventStream {
+    fn from_websocket<S>(messages: S) -> Self
+    where
+        S: Stream<Item = tungstenite::Result<Message>> + Send + 'static,
+    {
+        AccountEventStream {
+            inner: websocket_events(messages, parse_account_frame),
+        }
+    }
+}
+
+impl Stream for AccountEventStream {
+    type Item = Result<OrderEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures_util::stream;
     use mockito::Server;
 
     use super::*;
-    use crate::{utils::tests::create_test_config, wssession::session_manager::SessionManager};
+    use crate::{
+        utils::tests::{create_test_config, mock_websocket_server},
+        wssession::session_manager::SessionManager,
+        Error,
+    };
 
     // This test breaks if you have valid Config env vars set. The method we have to fix this
     // is the `crate::utils::with_env_vars()` method, but this method doesn't support async
@@ -238,4 +458,115 @@ mod tests {
         }
         mock.assert_async().await;
     }
+
+    #[test]
+    fn test_account_session_payload_get_message() {
+        let excluded = ["6YA00005".to_owned()];
+        let payload = AccountSessionPayload::builder()
+            .session_id("session-12345")
+            .exclude_accounts(&excluded)
+            .build();
+
+        let Message::Text(serialized) = payload.get_message().unwrap() else {
+            panic!("Expected a text WebSocket message");
+        };
+        assert_eq!(
+            serialized.as_str(),
+            r#"{"events":["order"],"sessionid":"session-12345","excludeAccounts":["6YA00005"]}"#
+        );
+
+        let payload = AccountSessionPayload::builder()
+            .session_id("session-12345")
+            .build();
+        let serialized = serde_json::to_string(&payload).unwrap();
+        assert!(!serialized.contains("excludeAccounts"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_order_events() {
+        let expected_session_id = "c8638963-a6d4-4fb9-9bc6-e25fbd8c60c3";
+        let expected_ws_host = "127.0.0.1";
+        let expected_ws_port = 9998u16;
+        let expected_ws_url = format!(
+            "ws://{}:{}/v1/accounts/events",
+            expected_ws_host, expected_ws_port
+        );
+        let excluded = ["6YA00006".to_owned()];
+        let mut server = Server::new_async().await;
+        let json_data = format!(
+            r#"{{"stream": {{"url": "{}", "sessionid": "{}"}}}}"#,
+            expected_ws_url, expected_session_id
+        );
+        let _mock = server
+            .mock("POST", "/v1/accounts/events/session")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json_data)
+            .create_async()
+            .await;
+
+        let expected_events = concat!(
+            r#"{"event":"heartbeat","status":"active","timestamp":1557777540}"#,
+            "\n",
+            r#"{"id":2197079,"event":"order","status":"filled","type":"market","price":0.0,"stop_price":0.0,"avg_fill_price":133.23,"executed_quantity":1.0,"last_fill_quantity":1.0,"last_fill_price":133.23,"remaining_quantity":0.0,"transaction_date":"2019-05-13T20:32:57.436Z","create_date":"2019-05-13T20:32:57.283Z","account":"6YA00005"}"#,
+        );
+        mock_websocket_server()
+            .address(expected_ws_host, expected_ws_port)
+            .expected_request(
+                AccountSessionPayload::builder()
+                    .session_id(expected_session_id)
+                    .exclude_accounts(&excluded)
+                    .build(),
+            )
+            .expected_response(expected_events)
+            .create()
+            .await;
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let account_session = AccountSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let events: Vec<_> = account_session
+            .ws_stream(
+                AccountSessionPayload::builder()
+                    .session_id(account_session.get_session_id())
+                    .exclude_accounts(&excluded)
+                    .build(),
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 1);
+        let event = events[0].as_ref().unwrap();
+        assert_eq!(event.id, 2197079);
+        assert_eq!(event.account, "6YA00005");
+        assert_eq!(event.status, crate::types::OrderStatus::Filled);
+        assert_eq!(event.avg_fill_price, 133.23);
+        assert_eq!(event.executed_quantity, 1.0);
+        assert_eq!(event.last_fill_quantity, 1.0);
+        assert_eq!(event.remaining_quantity, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_parse_errors_without_ending() {
+        let messages = stream::iter(vec![
+            Ok(Message::Text("{ not json".into())),
+            Ok(Message::Text(
+                r#"{"id":1,"event":"order","status":"open","type":"limit","price":"10.5","transaction_date":"2019-05-13T20:32:57.436Z","create_date":"2019-05-13T20:32:57.283Z","account":"6YA00005"}"#.into(),
+            )),
+            Err(tungstenite::Error::ConnectionClosed),
+            Ok(Message::Text("never read".into())),
+        ]);
+        let events: Vec<_> = AccountEventStream::from_websocket(messages).collect().await;
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], Err(Error::StreamEventParseError(_, _))));
+        let event = events[1].as_ref().unwrap();
+        assert_eq!(event.price, Some(10.5));
+        assert_eq!(event.executed_quantity, 0.0);
+        assert!(matches!(events[2], Err(Error::WebSocketError(_))));
+    }
 }
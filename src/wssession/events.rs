@@ -0,0 +1,468 @@
+//! # Streaming Event Types
+//!
+//! Typed representations of the payloads Tradier pushes over its streaming endpoints. Each
+//! market event variant corresponds to one of the [`MarketSessionFilter`] values a session can
+//! subscribe to; account sessions deliver [`OrderEvent`] values.
+//!
+//! Tradier encodes most numeric fields on streaming payloads as strings and timestamps as
+//! milliseconds since the Unix epoch, so the deserializers here accept both quoted and unquoted
+//! values.
+//!
+//! [`MarketSessionFilter`]: super::MarketSessionFilter
+use std::future::ready;
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{error, info};
+use tungstenite::Message;
+
+use crate::accounts::types::{OrderStatus, PriceType};
+use crate::utils::deserializers::{
+    datetime_from_millis, f64_lenient, option_f64_lenient, option_u64_lenient, u64_lenient,
+};
+use crate::{Error, Result};
+
+/// A single event received from a market data stream.
+///
+/// The variant is selected from the `type` field of the payload.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum MarketEvent {
+    /// Top-of-book update, sent for the `quote` filter.
+    Quote(Quote),
+    /// Last sale, sent for the `trade` filter.
+    Trade(Trade),
+    /// Session open/high/low/close summary, sent for the `summary` filter.
+    Summary(Summary),
+    /// Time and sale print, sent for the `timesale` filter.
+    Timesale(Timesale),
+    /// Extended trade, sent for the `tradex` filter.
+    Tradex(Trade),
+}
+
+impl MarketEvent {
+    /// Returns the symbol the event refers to.
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketEvent::Quote(quote) => &quote.symbol,
+            MarketEvent::Trade(trade) | MarketEvent::Tradex(trade) => &trade.symbol,
+            MarketEvent::Summary(summary) => &summary.symbol,
+            MarketEvent::Timesale(timesale) => &timesale.symbol,
+        }
+    }
+}
+
+/// Bid and ask update for a symbol.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Quote {
+    pub symbol: String,
+    #[serde(deserialize_with = "f64_lenient")]
+    pub bid: f64,
+    #[serde(rename = "bidsz", deserialize_with = "u64_lenient")]
+    pub bid_size: u64,
+    #[serde(rename = "bidexch")]
+    pub bid_exchange: String,
+    #[serde(rename = "biddate", deserialize_with = "datetime_from_millis")]
+    pub bid_date: DateTime<Utc>,
+    #[serde(deserialize_with = "f64_lenient")]
+    pub ask: f64,
+    #[serde(rename = "asksz", deserialize_with = "u64_lenient")]
+    pub ask_size: u64,
+    #[serde(rename = "askexch")]
+    pub ask_exchange: String,
+    #[serde(rename = "askdate", deserialize_with = "datetime_from_millis")]
+    pub ask_date: DateTime<Utc>,
+}
+
+/// Last sale for a symbol. Used for both `trade` and `tradex` events.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Trade {
+    pub symbol: String,
+    #[serde(rename = "exch")]
+    pub exchange: String,
+    #[serde(deserialize_with = "f64_lenient")]
+    pub price: f64,
+    #[serde(deserialize_with = "u64_lenient")]
+    pub size: u64,
+    /// Cumulative volume for the day.
+    #[serde(rename = "cvol", default, deserialize_with = "option_u64_lenient")]
+    pub cumulative_volume: Option<u64>,
+    #[serde(deserialize_with = "datetime_from_millis")]
+    pub date: DateTime<Utc>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub last: Option<f64>,
+}
+
+/// Daily summary for a symbol. Fields are absent until the first print of the day.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Summary {
+    pub symbol: String,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub open: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub high: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub low: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub close: Option<f64>,
+    #[serde(rename = "prevClose", default, deserialize_with = "option_f64_lenient")]
+    pub prev_close: Option<f64>,
+}
+
+/// Time and sale print for a symbol.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Timesale {
+    pub symbol: String,
+    #[serde(rename = "exch")]
+    pub exchange: String,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub bid: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub ask: Option<f64>,
+    #[serde(deserialize_with = "f64_lenient")]
+    pub last: f64,
+    #[serde(deserialize_with = "u64_lenient")]
+    pub size: u64,
+    #[serde(deserialize_with = "datetime_from_millis")]
+    pub date: DateTime<Utc>,
+    #[serde(default, deserialize_with = "option_u64_lenient")]
+    pub seq: Option<u64>,
+    #[serde(default)]
+    pub flag: String,
+    #[serde(default)]
+    pub cancel: bool,
+    #[serde(default)]
+    pub correction: bool,
+    #[serde(default)]
+    pub session: String,
+}
+
+/// A change to an order, delivered by an account session.
+///
+/// Tradier sends one event every time an order is placed, filled, partially filled, modified or
+/// canceled, for every account of the user unless it was excluded when subscribing.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct OrderEvent {
+    pub id: u64,
+    /// The account the order belongs to.
+    pub account: String,
+    pub status: OrderStatus,
+    #[serde(rename = "type")]
+    pub order_type: PriceType,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub price: Option<f64>,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub stop_price: Option<f64>,
+    #[serde(default, deserialize_with = "f64_lenient")]
+    pub avg_fill_price: f64,
+    #[serde(default, deserialize_with = "f64_lenient")]
+    pub executed_quantity: f64,
+    #[serde(default, deserialize_with = "f64_lenient")]
+    pub last_fill_quantity: f64,
+    #[serde(default, deserialize_with = "option_f64_lenient")]
+    pub last_fill_price: Option<f64>,
+    #[serde(default, deserialize_with = "f64_lenient")]
+    pub remaining_quantity: f64,
+    pub transaction_date: DateTime<Utc>,
+    pub create_date: DateTime<Utc>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// The id of the parent order, set on the legs of multileg and advanced orders.
+    #[serde(default, deserialize_with = "option_u64_lenient")]
+    pub parent_id: Option<u64>,
+}
+
+/// Parses a single text frame into market events.
+///
+/// A frame usually carries one JSON payload, but when `linebreak` is enabled Tradier may batch
+/// several newline-terminated payloads together. Each payload yields one item, so a malformed
+/// payload does not prevent the remaining ones from being delivered: once a syntax error is
+/// found, the rest of the frame is parsed line by line.
+pub(crate) fn parse_market_frame(frame: &str) -> Vec<Result<MarketEvent>> {
+    parse_frame(frame, |value| Some(event_from_value(value)))
+}
+
+/// Parses a single text frame into order events, skipping the heartbeats Tradier sends to keep
+/// the account socket alive.
+pub(crate) fn parse_account_frame(frame: &str) -> Vec<Result<OrderEvent>> {
+    parse_frame(frame, |value| {
+        match value.get("event").and_then(Value::as_str) {
+            Some("heartbeat") => None,
+            _ => Some(event_from_value(value)),
+        }
+    })
+}
+
+fn parse_frame<T>(frame: &str, convert: impl Fn(Value) -> Option<Result<T>>) -> Vec<Result<T>> {
+    let mut events = Vec::new();
+    let mut values = serde_json::Deserializer::from_str(frame).into_iter::<Value>();
+    while let Some(value) = values.next() {
+        match value {
+            Ok(value) => events.extend(convert(value)),
+            Err(_) => {
+                let remainder = &frame[values.byte_offset()..];
+                events.extend(
+                    remainder
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .filter_map(|line| match serde_json::from_str::<Value>(line) {
+                            Ok(value) => convert(value),
+                            Err(e) => Some(Err(Error::StreamEventParseError(line.to_owned(), e))),
+                        }),
+                );
+                break;
+            }
+        }
+    }
+    events
+}
+
+fn event_from_value<T: DeserializeOwned>(value: Value) -> Result<T> {
+    T::deserialize(&value).map_err(|e| Error::StreamEventParseError(value.to_string(), e))
+}
+
+/// Turns the messages of a WebSocket connection into a stream of typed events.
+///
+/// Text and binary frames are handed to `parse`, one item per payload. Parse errors do not end
+/// the stream; it ends when the connection is closed, or after yielding a
+/// [`Error::WebSocketError`].
+pub(crate) fn websocket_events<T, S>(
+    messages: S,
+    parse: fn(&str) -> Vec<Result<T>>,
+) -> BoxStream<'static, Result<T>>
+where
+    T: Send + 'static,
+    S: Stream<Item = tungstenite::Result<Message>> + Send + 'static,
+{
+    messages
+        .scan(false, move |failed, message| {
+            if *failed {
+                return ready(None);
+            }
+            let events = match message {
+                Ok(Message::Text(text)) => parse(&text),
+                Ok(Message::Binary(data)) => parse_utf8(&data, parse),
+                Ok(Message::Close(frame)) => {
+                    info!("Connection closed: {:?}", frame);
+                    return ready(None);
+                }
+                Ok(_) => vec![],
+                Err(e) => {
+                    error!("Error: {}", e);
+                    *failed = true;
+                    vec![Err(Error::WebSocketError(Box::new(e)))]
+                }
+            };
+            ready(Some(stream::iter(events)))
+        })
+        .flatten()
+        .boxed()
+}
+
+/// Turns the chunks of a streaming HTTP response body into a stream of typed events.
+///
+/// Payloads are newline-terminated, but a chunk may end in the middle of one, so only the
+/// complete lines of the buffered body are handed to `parse`; whatever is left when the body
+/// ends is parsed last. A failed read is yielded as [`Error::NetworkError`] and ends the stream.
+pub(crate) fn http_events<T, B, S>(
+    chunks: S,
+    parse: fn(&str) -> Vec<Result<T>>,
+) -> BoxStream<'static, Result<T>>
+where
+    T: Send + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    S: Stream<Item = reqwest::Result<B>> + Send + 'static,
+{
+    chunks
+        .map(Some)
+        .chain(stream::once(ready(None)))
+        .scan((Vec::new(), false), move |(buffer, failed), chunk| {
+            if *failed {
+                return ready(None);
+            }
+            let events = match chunk {
+                Some(Ok(bytes)) => {
+                    buffer.extend_from_slice(bytes.as_ref());
+                    match buffer.iter().rposition(|byte| *byte == b'\n') {
+                        Some(end) => {
+                            let lines: Vec<u8> = buffer.drain(..=end).collect();
+                            parse_utf8(&lines, parse)
+                        }
+                        None => vec![],
+                    }
+                }
+                Some(Err(e)) => {
+                    error!("Error: {}", e);
+                    *failed = true;
+                    vec![Err(Error::NetworkError(e))]
+                }
+                None if buffer.is_empty() => vec![],
+                None => parse_utf8(&std::mem::take(buffer), parse),
+            };
+            ready(Some(stream::iter(events)))
+        })
+        .flatten()
+        .boxed()
+}
+
+/// Hands `data` to `parse` if it is valid UTF-8. Otherwise yields a single
+/// [`Error::StreamEventParseError`], like any other payload that cannot be parsed, so that one
+/// bad frame does not end the stream.
+fn parse_utf8<T>(data: &[u8], parse: fn(&str) -> Vec<Result<T>>) -> Vec<Result<T>> {
+    match std::str::from_utf8(data) {
+        Ok(text) => parse(text),
+        Err(e) => vec![Err(Error::StreamEventParseError(
+            String::from_utf8_lossy(data).into_owned(),
+            serde_json::Error::io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        ))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const QUOTE: &str = r#"{"type":"quote","symbol":"C","bid":281.84,"bidsz":60,"bidexch":"M","biddate":"1557757189000","ask":281.85,"asksz":6,"askexch":"Z","askdate":"1557757190000"}"#;
+    const TRADE: &str = r#"{"type":"trade","symbol":"SPY","exch":"J","price":"281.1","size":"100","cvol":"7","date":"1557757200000","last":"281.1"}"#;
+    const SUMMARY: &str = r#"{"type":"summary","symbol":"SPY","open":"282.42","high":"282.42","low":"281.12","prevClose":"281.75"}"#;
+    const TIMESALE: &str = r#"{"type":"timesale","symbol":"SPY","exch":"Q","bid":"281.09","ask":"281.1","last":"281.1","size":"100","date":"1557758874355","seq":352402,"flag":"","cancel":false,"correction":false,"session":"normal"}"#;
+    const TRADEX: &str = r#"{"type":"tradex","symbol":"SPY","exch":"Q","price":"281.1","size":"100","cvol":"5","date":"1557758874355","last":"281.1"}"#;
+
+    #[test]
+    fn test_parse_quote() {
+        let event: MarketEvent = serde_json::from_str(QUOTE).unwrap();
+        let MarketEvent::Quote(quote) = event else {
+            panic!("expected a quote, got {event:?}");
+        };
+        assert_eq!(quote.symbol, "C");
+        assert_eq!(quote.bid, 281.84);
+        assert_eq!(quote.bid_size, 60);
+        assert_eq!(quote.bid_exchange, "M");
+        assert_eq!(quote.bid_date.timestamp_millis(), 1557757189000);
+        assert_eq!(quote.ask, 281.85);
+        assert_eq!(quote.ask_size, 6);
+        assert_eq!(quote.ask_exchange, "Z");
+    }
+
+    #[test]
+    fn test_parse_trade() {
+        let event: MarketEvent = serde_json::from_str(TRADE).unwrap();
+        let MarketEvent::Trade(trade) = event else {
+            panic!("expected a trade, got {event:?}");
+        };
+        assert_eq!(trade.price, 281.1);
+        assert_eq!(trade.size, 100);
+        assert_eq!(trade.cumulative_volume, Some(7));
+        assert_eq!(trade.last, Some(281.1));
+    }
+
+    #[test]
+    fn test_parse_summary_with_missing_close() {
+        let event: MarketEvent = serde_json::from_str(SUMMARY).unwrap();
+        let MarketEvent::Summary(summary) = event else {
+            panic!("expected a summary, got {event:?}");
+        };
+        assert_eq!(summary.open, Some(282.42));
+        assert_eq!(summary.prev_close, Some(281.75));
+        assert_eq!(summary.close, None);
+    }
+
+    #[test]
+    fn test_parse_timesale() {
+        let event: MarketEvent = serde_json::from_str(TIMESALE).unwrap();
+        let MarketEvent::Timesale(timesale) = event else {
+            panic!("expected a timesale, got {event:?}");
+        };
+        assert_eq!(timesale.bid, Some(281.09));
+        assert_eq!(timesale.seq, Some(352402));
+        assert_eq!(timesale.session, "normal");
+        assert!(!timesale.cancel);
+    }
+
+    #[test]
+    fn test_parse_tradex() {
+        let event: MarketEvent = serde_json::from_str(TRADEX).unwrap();
+        assert!(matches!(event, MarketEvent::Tradex(_)));
+        assert_eq!(event.symbol(), "SPY");
+    }
+
+    #[test]
+    fn test_parse_market_frame_with_multiple_lines() {
+        let frame = format!("{QUOTE}\n{TRADE}\n\n");
+        let events = parse_market_frame(&frame);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.is_ok()));
+    }
+
+    #[test]
+    fn test_parse_market_frame_reports_malformed_lines_individually() {
+        let frame = format!("{QUOTE}\n{{ not json\n{TRADE}");
+        let events = parse_market_frame(&frame);
+        assert_eq!(events.len(), 3);
+        assert!(events[0].is_ok());
+        assert!(matches!(
+            &events[1],
+            Err(Error::StreamEventParseError(payload, _)) if payload == "{ not json"
+        ));
+        assert!(events[2].is_ok());
+    }
+
+    #[test]
+    fn test_parse_market_frame_with_pretty_printed_payload() {
+        let pretty = serde_json::to_string_pretty(
+            &serde_json::from_str::<serde_json::Value>(QUOTE).unwrap(),
+        )
+        .unwrap();
+        let events = parse_market_frame(&pretty);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Ok(MarketEvent::Quote(_))));
+    }
+
+    #[test]
+    fn test_parse_unknown_event_type_is_an_error() {
+        let events = parse_market_frame(r#"{"type":"unknown","symbol":"SPY"}"#);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_err());
+    }
+
+    #[test]
+    fn test_parse_account_frame_skips_heartbeats() {
+        let frame = concat!(
+            r#"{"event":"heartbeat","status":"active","timestamp":1557777540}"#,
+            "\n",
+            r#"{"id":2197081,"event":"order","status":"partially_filled","type":"limit","price":"134.0","avg_fill_price":"133.9","executed_quantity":"40","last_fill_quantity":"40","last_fill_price":"133.9","remaining_quantity":"60","transaction_date":"2019-05-13T20:32:57.436Z","create_date":"2019-05-13T20:32:57.283Z","account":"6YA00005","tag":"my-tag","parent_id":"2197080"}"#,
+        );
+        let events = parse_account_frame(frame);
+        assert_eq!(events.len(), 1);
+        let event = events[0].as_ref().unwrap();
+        assert_eq!(event.status, OrderStatus::PartiallyFilled);
+        assert_eq!(event.price, Some(134.0));
+        assert_eq!(event.executed_quantity, 40.0);
+        assert_eq!(event.remaining_quantity, 60.0);
+        assert_eq!(event.last_fill_price, Some(133.9));
+        assert_eq!(event.tag.as_deref(), Some("my-tag"));
+        assert_eq!(event.parent_id, Some(2197080));
+    }
+
+    #[tokio::test]
+    async fn test_http_events_reassembles_payloads_split_across_chunks() {
+        let (head, tail) = TRADE.split_at(20);
+        let chunks = stream::iter(vec![
+            Ok(format!("{QUOTE}\n{head}")),
+            Ok(format!("{tail}\n")),
+            Ok(SUMMARY.to_owned()),
+        ]);
+        let events: Vec<_> = http_events(chunks, parse_market_frame).collect().await;
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], Ok(MarketEvent::Quote(_))));
+        assert!(matches!(events[1], Ok(MarketEvent::Trade(_))));
+        assert!(matches!(events[2], Ok(MarketEvent::Summary(_))));
+    }
+}
@@ -1,10 +1,21 @@
-use crate::config::Config;
+use crate::config::{Config, StreamTransport};
+use crate::streaming::{BackpressurePolicy, BufferedStream};
+use crate::wssession::events::{http_events, parse_market_frame, websocket_events, MarketEvent};
+use crate::wssession::reconnect::{ReconnectPolicy, ReconnectingStream, Resubscribe, Subscription};
+use crate::wssession::recording::FrameRecorder;
 use crate::wssession::session::{Session, SessionType};
+use crate::wssession::subscription::SubscriptionHandle;
 use crate::{Error, Result};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{SinkExt, Stream, StreamExt};
+use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use std::future::pending;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::watch;
 use tokio_tungstenite::connect_async;
 use tracing::{error, info};
 use tungstenite::Message;
@@ -131,6 +142,40 @@ impl<'a> MarketSessionPayload<'a> {
             .build()
     }
 
+    /// Copies the borrowed parts of the payload, so that it can be sent again by a
+    /// [`ReconnectingStream`].
+    pub fn into_owned(self) -> MarketSessionPayload<'static> {
+        MarketSessionPayload {
+            symbols: Cow::Owned(self.symbols.into_owned()),
+            filters: self.filters.map(|filters| Cow::Owned(filters.into_owned())),
+            session_id: Cow::Owned(self.session_id.into_owned()),
+            linebreak: self.linebreak,
+            valid_only: self.valid_only,
+            advanced_details: self.advanced_details,
+        }
+    }
+
+    /// Encodes the payload as the form fields of an HTTP streaming request, where lists are
+    /// comma-separated.
+    pub(crate) fn form_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("symbols", self.symbols.join(",")),
+            ("sessionid", self.session_id.to_string()),
+            ("linebreak", true.to_string()),
+        ];
+        if let Some(filters) = &self.filters {
+            let filters: Vec<&str> = filters.iter().map(AsRef::as_ref).collect();
+            params.push(("filter", filters.join(",")));
+        }
+        if let Some(valid_only) = self.valid_only {
+            params.push(("validOnly", valid_only.to_string()));
+        }
+        if let Some(advanced_details) = self.advanced_details {
+            params.push(("advancedDetails", advanced_details.to_string()));
+        }
+        params
+    }
+
     /// Converts the payload to a WebSocket `Message` for sending.
     ///
     /// # Returns
@@ -170,8 +215,16 @@ impl<'a> Display for MarketSessionPayload<'a> {
 }
 
 /// Represents a market session that can be used to receive streaming data
-/// from the Tradier WebSocket API.
-pub struct MarketSession<'a>(Session<'a>);
+/// from the Tradier WebSocket or HTTP streaming API.
+///
+/// Only one `MarketSession` can exist at a time; its slot is released when it is dropped, after
+/// which a new one can be created. An `AccountSession` may be open alongside it.
+pub struct MarketSession<'a> {
+    session: Session<'a>,
+    http_url: String,
+    transport: StreamTransport,
+    recorder: Option<FrameRecorder>,
+}
 
 impl<'a> MarketSession<'a> {
     /// Creates a new `MarketSession` using the specified configuration.
@@ -190,9 +243,17 @@ impl<'a> MarketSession<'a> {
         config: &Config,
         session_manager: &'a SessionManager,
     ) -> Result<Self> {
-        Ok(MarketSession(
-            Session::new_with_session_manager(session_manager, SessionType::Market, config).await?,
-        ))
+        Ok(MarketSession {
+            session: Session::new_with_session_manager(
+                session_manager,
+                SessionType::Market,
+                config,
+            )
+            .await?,
+            http_url: config.get_http_url(),
+            transport: config.streaming.transport,
+            recorder: None,
+        })
     }
 
     /// Retrieves the session ID associated with the `MarketSession`.
@@ -200,7 +261,7 @@ impl<'a> MarketSession<'a> {
     /// # Returns
     /// - `&str`: The session ID string.
     pub fn get_session_id(&self) -> &str {
-        self.0.get_session_id()
+        self.session.get_session_id()
     }
 
     /// Retrieves the WebSocket URL for the `MarketSession`.
@@ -208,57 +269,455 @@ impl<'a> MarketSession<'a> {
     /// # Returns
     /// - `&str`: The WebSocket URL string.
     pub fn get_websocket_url(&self) -> &str {
-        self.0.get_websocket_url()
+        self.session.get_websocket_url()
+    }
+
+    /// Retrieves the HTTP streaming URL for the `MarketSession`, taken from
+    /// [`Config::get_http_url`] when the session was created.
+    ///
+    /// # Returns
+    /// - `&str`: The HTTP streaming URL string.
+    pub fn get_http_url(&self) -> &str {
+        &self.http_url
+    }
+
+    /// Returns the transport [`stream`](MarketSession::stream) uses, as configured by
+    /// `StreamingConfig::transport`.
+    pub fn transport(&self) -> StreamTransport {
+        self.transport
+    }
+
+    /// Records the raw frames of the WebSocket streams opened from now on, including those of
+    /// reconnections, or stops recording with `None`. Frames received over the HTTP transport
+    /// are not recorded.
+    pub fn set_recorder(&mut self, recorder: Option<FrameRecorder>) {
+        self.recorder = recorder;
+    }
+
+    /// Streams typed events over the configured transport.
+    ///
+    /// Dispatches to [`ws_stream`](MarketSession::ws_stream) or
+    /// [`http_stream`](MarketSession::http_stream); both yield the same events.
+    pub async fn stream(&self, payload: MarketSessionPayload<'a>) -> Result<MarketEventStream> {
+        match self.transport {
+            StreamTransport::WebSocket => self.ws_stream(payload).await,
+            StreamTransport::Http => self.http_stream(payload).await,
+        }
     }
 
-    /// Initiates a WebSocket connection and streams data based on the provided payload.
+    /// Initiates a WebSocket connection and streams typed events based on the provided payload.
     ///
     /// # Arguments
     /// - `payload`: A `MarketSessionPayload` specifying symbols and settings for the WebSocket session.
     ///
     /// # Returns
-    /// - `Ok(())`: If the WebSocket connection was successfully managed.
-    /// - `Err(Box<dyn Error>)`: If the WebSocket connection or data streaming fails.
+    /// - `Ok(MarketEventStream)`: A stream of [`MarketEvent`] values once the subscription was sent.
+    /// - `Err(Error)`: If the WebSocket connection or the subscription request fails.
     ///
     /// # Behavior
     /// - Connects to the WebSocket endpoint.
     /// - Sends the specified `MarketSessionPayload`.
-    /// - Listens for incoming messages and logs text or binary data.
-    /// - Terminates on connection close or error.
-    pub async fn ws_stream(&self, payload: MarketSessionPayload<'a>) -> Result<()> {
-        let uri = &self.0.stream_info.url;
+    /// - Yields one item per received payload. Payloads that cannot be parsed are yielded as
+    ///   [`Error::StreamEventParseError`] without terminating the stream.
+    /// - Terminates on connection close, or after yielding a [`Error::WebSocketError`].
+    pub async fn ws_stream(&self, payload: MarketSessionPayload<'a>) -> Result<MarketEventStream> {
+        let uri = self.get_websocket_url();
         let url = Url::parse(uri)?;
 
         info!("Connecting to: {}", uri);
         let (ws_stream, _) = connect_async(url.as_str()).await.map_err(Box::new)?;
-        let (mut write, mut read) = ws_stream.split();
+        let (mut write, read) = ws_stream.split();
 
         let message = payload.get_message()?;
         write.send(message).await.map_err(Box::new)?;
         info!("Sent payload: {}", payload);
 
-        while let Some(message) = read.next().await {
-            match message {
-                Ok(Message::Text(text)) => {
-                    info!("Received: {}", text);
-                }
-                Ok(Message::Binary(data)) => {
-                    info!("Received binary data: {} bytes", data.len());
-                    // Handle binary data as needed
-                }
-                Ok(Message::Close(frame)) => {
-                    info!("Connection closed: {:?}", frame);
-                    break;
+        Ok(MarketEventStream::from_websocket(
+            read,
+            self.recorder.clone(),
+        ))
+    }
+
+    /// Opens an HTTP streaming connection and streams typed events based on the provided payload.
+    ///
+    /// # Arguments
+    /// - `payload`: A `MarketSessionPayload` specifying symbols and settings for the session.
+    ///
+    /// # Returns
+    /// - `Ok(MarketEventStream)`: A stream of [`MarketEvent`] values once Tradier accepted the
+    ///   subscription.
+    /// - `Err(Error)`: If the request fails or Tradier answers with an error status.
+    ///
+    /// # Behavior
+    /// - POSTs the payload as a form to [`get_http_url`](MarketSession::get_http_url). Line
+    ///   breaks are always requested, since they delimit payloads in the response body.
+    /// - Yields one item per received payload. Payloads that cannot be parsed are yielded as
+    ///   [`Error::StreamEventParseError`] without terminating the stream.
+    /// - Terminates when the response body ends, or after yielding a [`Error::NetworkError`].
+    pub async fn http_stream(
+        &self,
+        payload: MarketSessionPayload<'a>,
+    ) -> Result<MarketEventStream> {
+        open_http_stream(&self.http_url, &payload).await
+    }
+
+    /// Streams typed events over the configured transport, reconnecting whenever the
+    /// connection is lost.
+    ///
+    /// # Arguments
+    /// - `config`: The configuration used to create new sessions once the current one expired.
+    /// - `payload`: The subscription, sent again on every reconnection. Its session id is
+    ///   replaced by the id of the current session each time.
+    /// - `policy`: The backoff between reconnection attempts, e.g.
+    ///   [`ReconnectPolicy::from_config`].
+    ///
+    /// # Returns
+    /// - `Ok(ReconnectingStream)`: Once the first subscription was sent.
+    /// - `Err(Error)`: If the first connection fails.
+    pub async fn reconnecting_stream(
+        self,
+        config: &Config,
+        payload: MarketSessionPayload<'_>,
+        policy: ReconnectPolicy,
+    ) -> Result<ReconnectingStream<'a, MarketEvent>> {
+        let (events, _) = self.subscribe(config, payload, policy).await?;
+        Ok(events)
+    }
+
+    /// Like [`reconnecting_stream`](MarketSession::reconnecting_stream), but also returns a
+    /// [`SubscriptionHandle`] to change the symbols and filters while the stream runs.
+    ///
+    /// Over a WebSocket, every change is sent right away as an updated payload on the open
+    /// connection. An HTTP streaming response cannot be updated, so the request is made again
+    /// right away with the new payload and replaces the previous response, without a
+    /// reconnection. In both cases reconnections subscribe to the latest symbols and filters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use tradier::Config;
+    /// use tradier::wssession::{MarketSession, MarketSessionPayload, ReconnectPolicy};
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let config = Config::new();
+    ///     let session = MarketSession::new(&config).await.unwrap();
+    ///     let symbols = ["SPY".to_owned()];
+    ///     let payload = MarketSessionPayload::recommended(&symbols, "");
+    ///     let (mut events, handle) = session
+    ///         .subscribe(&config, payload, ReconnectPolicy::from_config(&config))
+    ///         .await
+    ///         .unwrap();
+    ///     handle.add_symbols(["AAPL", "MSFT"]);
+    ///     while let Some(event) = events.next().await {
+    ///         println!("{:?}", event);
+    ///     }
+    /// }
+    /// ```
+    pub async fn subscribe(
+        self,
+        config: &Config,
+        payload: MarketSessionPayload<'_>,
+        policy: ReconnectPolicy,
+    ) -> Result<(ReconnectingStream<'a, MarketEvent>, SubscriptionHandle)> {
+        let (handle, updates) = SubscriptionHandle::new(payload.into_owned());
+        let mut subscription = Subscription {
+            session: self,
+            config: config.clone(),
+            payload: updates,
+        };
+        let events = subscription.open().await?;
+        Ok((
+            ReconnectingStream::new(subscription, events, policy),
+            handle,
+        ))
+    }
+
+    /// Like [`subscribe`](MarketSession::subscribe), but opens the first connection from within
+    /// the returned stream, retrying it under `policy` instead of failing.
+    pub(crate) fn subscribe_retrying(
+        self,
+        config: &Config,
+        payload: MarketSessionPayload<'_>,
+        policy: ReconnectPolicy,
+    ) -> (ReconnectingStream<'a, MarketEvent>, SubscriptionHandle) {
+        let (handle, updates) = SubscriptionHandle::new(payload.into_owned());
+        let subscription = Subscription {
+            session: self,
+            config: config.clone(),
+            payload: updates,
+        };
+        (ReconnectingStream::connecting(subscription, policy), handle)
+    }
+
+    /// Streams over HTTP and, whenever `updates` changes, replaces the response with a new
+    /// request for the latest payload, until the handles are dropped. A failed request ends the
+    /// stream after yielding its error.
+    async fn http_stream_with_updates(
+        &self,
+        mut updates: watch::Receiver<MarketSessionPayload<'static>>,
+    ) -> Result<BoxStream<'static, Result<MarketEvent>>> {
+        let http_url = self.http_url.clone();
+        let session_id = self.get_session_id().to_owned();
+
+        let payload = with_session_id(updates.borrow_and_update().clone(), &session_id);
+        let events = open_http_stream(&http_url, &payload).await?.boxed();
+
+        let events = stream::unfold((events, Some(updates)), move |(mut events, mut updates)| {
+            let http_url = http_url.clone();
+            let session_id = session_id.clone();
+            async move {
+                loop {
+                    let changed = async {
+                        match updates.as_mut() {
+                            Some(updates) => updates.changed().await.is_ok(),
+                            None => pending().await,
+                        }
+                    };
+                    tokio::select! {
+                        event = events.next() => {
+                            return event.map(|event| (event, (events, updates)));
+                        }
+                        changed = changed => {
+                            let Some(receiver) = updates.as_mut().filter(|_| changed) else {
+                                updates = None;
+                                continue;
+                            };
+                            let payload = with_session_id(
+                                receiver.borrow_and_update().clone(),
+                                &session_id,
+                            );
+                            match open_http_stream(&http_url, &payload).await {
+                                Ok(restarted) => events = restarted.boxed(),
+                                Err(e) => {
+                                    return Some((Err(e), (stream::empty().boxed(), None)));
+                                }
+                            }
+                        }
+                    }
                 }
-                Err(e) => {
-                    error!("Error: {}", e);
-                    break;
+            }
+        });
+        Ok(events.boxed())
+    }
+
+    /// Connects over a WebSocket and keeps sending the latest payload of `updates` on the open
+    /// connection until the handles are dropped.
+    async fn ws_stream_with_updates(
+        &self,
+        mut updates: watch::Receiver<MarketSessionPayload<'static>>,
+    ) -> Result<MarketEventStream> {
+        let uri = self.get_websocket_url();
+        let session_id = self.get_session_id().to_owned();
+
+        info!("Connecting to: {}", uri);
+        let (ws_stream, _) = connect_async(Url::parse(uri)?.as_str())
+            .await
+            .map_err(Box::new)?;
+        let (mut write, read) = ws_stream.split();
+
+        let payload = with_session_id(updates.borrow_and_update().clone(), &session_id);
+        write.send(payload.get_message()?).await.map_err(Box::new)?;
+        info!("Sent payload: {}", payload);
+
+        let messages = stream::unfold(
+            (read, write, Some(updates)),
+            move |(mut read, mut write, mut updates)| {
+                let session_id = session_id.clone();
+                async move {
+                    loop {
+                        let changed = async {
+                            match updates.as_mut() {
+                                Some(updates) => updates.changed().await.is_ok(),
+                                None => pending().await,
+                            }
+                        };
+                        tokio::select! {
+                            message = read.next() => {
+                                return message.map(|message| (message, (read, write, updates)));
+                            }
+                            changed = changed => {
+                                let Some(receiver) = updates.as_mut().filter(|_| changed) else {
+                                    updates = None;
+                                    continue;
+                                };
+                                let payload = with_session_id(
+                                    receiver.borrow_and_update().clone(),
+                                    &session_id,
+                                );
+                                let sent = match payload.get_message() {
+                                    Ok(message) => write.send(message).await,
+                                    Err(e) => {
+                                        error!("Unable to serialize {}: {}", payload, e);
+                                        continue;
+                                    }
+                                };
+                                match sent {
+                                    Ok(()) => info!("Sent payload: {}", payload),
+                                    Err(e) => return Some((Err(e), (read, write, updates))),
+                                }
+                            }
+                        }
+                    }
                 }
+            },
+        );
+        Ok(MarketEventStream::from_websocket(
+            messages,
+            self.recorder.clone(),
+        ))
+    }
+}
+
+/// POSTs `payload` to the HTTP streaming endpoint at `http_url`, as described in
+/// [`MarketSession::http_stream`].
+async fn open_http_stream(
+    http_url: &str,
+    payload: &MarketSessionPayload<'_>,
+) -> Result<MarketEventStream> {
+    info!("Connecting to: {}", http_url);
+    let response = HttpClient::new()
+        .post(http_url)
+        .header("Accept", "application/json")
+        .form(&payload.form_params())
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await?;
+        return Err(Error::from_response_body(status, &body));
+    }
+    info!("Sent payload: {}", payload);
+
+    Ok(MarketEventStream::from_http(response))
+}
+
+fn with_session_id(
+    mut payload: MarketSessionPayload<'static>,
+    session_id: &str,
+) -> MarketSessionPayload<'static> {
+    payload.session_id = Cow::Owned(session_id.to_owned());
+    payload
+}
+
+impl<'a> Subscription<MarketSession<'a>, watch::Receiver<MarketSessionPayload<'static>>> {
+    async fn open(&mut self) -> Result<BoxStream<'static, Result<MarketEvent>>> {
+        match self.session.transport {
+            StreamTransport::WebSocket => Ok(self
+                .session
+                .ws_stream_with_updates(self.payload.clone())
+                .await?
+                .boxed()),
+            StreamTransport::Http => {
+                self.session
+                    .http_stream_with_updates(self.payload.clone())
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> Resubscribe
+    for Subscription<MarketSession<'a>, watch::Receiver<MarketSessionPayload<'static>>>
+{
+    type Event = MarketEvent;
+
+    async fn resubscribe(
+        &mut self,
+        renew_session: bool,
+    ) -> Result<(BoxStream<'static, Result<MarketEvent>>, bool)> {
+        let renew_session = renew_session || self.session.session.is_expired();
+        if renew_session {
+            self.session.session.renew(&self.config).await?;
+        }
+        Ok((self.open().await?, renew_session))
+    }
+
+    fn disconnected(&mut self) {
+        self.session.session.mark_used();
+    }
+}
+
+/// A stream of typed [`MarketEvent`] values received from a market session.
+///
+/// Each item is either a successfully parsed event or the error that prevented a payload from
+/// being parsed. Parse errors do not end the stream; it only ends when the connection is closed
+/// or a transport error occurs.
+pub struct MarketEventStream {
+    inner: BoxStream<'static, Result<MarketEvent>>,
+}
+
+impl MarketEventStream {
+    fn from_websocket<S>(messages: S, recorder: Option<FrameRecorder>) -> Self
+    where
+        S: Stream<Item = tungstenite::Result<Message>> + Send + 'static,
+    {
+        let messages = messages.inspect(move |message| {
+            let Some(recorder) = &recorder else {
+                return;
+            };
+            match message {
+                Ok(Message::Text(text)) => recorder.record(text),
+                Ok(Message::Binary(data)) => match std::str::from_utf8(data) {
+                    Ok(text) => recorder.record(text),
+                    Err(_) => error!("Not recording binary frame that is not UTF-8"),
+                },
                 _ => {}
             }
+        });
+        MarketEventStream {
+            inner: websocket_events(messages, parse_market_frame),
         }
+    }
 
-        Ok(())
+    /// Parses recorded frames the way frames received over a WebSocket are parsed. Errors are
+    /// passed through.
+    pub(crate) fn from_frames<S>(frames: S) -> Self
+    where
+        S: Stream<Item = Result<String>> + Send + 'static,
+    {
+        let inner = frames
+            .flat_map(|frame| {
+                stream::iter(match frame {
+                    Ok(frame) => parse_market_frame(&frame),
+                    Err(e) => vec![Err(e)],
+                })
+            })
+            .boxed();
+        MarketEventStream { inner }
+    }
+
+    fn from_http(response: reqwest::Response) -> Self {
+        let chunks = stream::unfold(Some(response), |response| async move {
+            let mut response = response?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        });
+        MarketEventStream {
+            inner: http_events(chunks, parse_market_frame),
+        }
+    }
+
+    /// Reads the stream ahead into a buffer of `capacity` events that applies `policy` when the
+    /// consumer falls behind. Must be called from within a Tokio runtime.
+    pub fn with_backpressure(
+        self,
+        capacity: usize,
+        policy: BackpressurePolicy,
+    ) -> BufferedStream<Result<MarketEvent>> {
+        BufferedStream::new(self, capacity, policy)
+    }
+}
+
+impl Stream for MarketEventStream {
+    type Item = Result<MarketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
     }
 }
 
@@ -270,7 +729,9 @@ mod tests {
     };
 
     use super::*;
+    use futures_util::stream;
     use mockito::Server;
+    use std::future::ready;
 
     #[test]
     fn test_market_session_filter_as_ref() {
@@ -451,7 +912,7 @@ mod tests {
         );
 
         let expected_event = r#"
-        {{
+        {
             "type": "quote",
             "symbol": "C",
             "bid": 281.84,
@@ -462,7 +923,7 @@ mod tests {
             "asksz": 6,
             "askexch": "Z",
             "askdate": "1557757190000"
-        }}"#;
+        }"#;
         let _mock = server
             .mock("POST", "/v1/markets/events/session")
             .with_status(200)
@@ -497,6 +958,118 @@ mod tests {
             )
             .await;
         assert!(result.is_ok());
+
+        let events: Vec<_> = result.unwrap().collect().await;
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            Ok(MarketEvent::Quote(quote)) => {
+                assert_eq!(quote.symbol, "C");
+                assert_eq!(quote.bid, 281.84);
+                assert_eq!(quote.ask_size, 6);
+            }
+            other => panic!("Expected a quote event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recorded_session_replays_the_same_events() {
+        let session_id = "recorded-session";
+        let ws_port = 9994u16;
+        let symbols = ["SPY".to_owned()];
+        let frame = r#"{"type":"summary","symbol":"SPY","open":"282.42","prevClose":"281.75"}"#;
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://127.0.0.1:{ws_port}/v1/markets/events","sessionid":"{session_id}"}}}}"#
+            ))
+            .create_async()
+            .await;
+        let config = create_test_config()
+            .server_url(&server.url())
+            .web_socket_url(&format!("ws://127.0.0.1:{ws_port}"))
+            .web_socket_path("/v1/markets/events")
+            .finish();
+        mock_websocket_server()
+            .address("127.0.0.1", ws_port)
+            .expected_request(
+                MarketSessionPayload::builder()
+                    .session_id(session_id)
+                    .symbols(&symbols)
+                    .build(),
+            )
+            .expected_response(frame)
+            .create()
+            .await;
+
+        let path = std::env::temp_dir().join(format!("tradier-session-{}.rec", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let recorder = FrameRecorder::create(&path).await.unwrap();
+        let session_manager = SessionManager::default();
+        let mut market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        market_session.set_recorder(Some(recorder.clone()));
+        let live: Vec<_> = market_session
+            .ws_stream(
+                MarketSessionPayload::builder()
+                    .session_id(session_id)
+                    .symbols(&symbols)
+                    .build(),
+            )
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        recorder.flush().await.unwrap();
+
+        let replayed: Vec<_> = crate::wssession::FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .events(crate::wssession::ReplaySpeed::AsFastAsPossible)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(live.len(), 1);
+        assert_eq!(replayed, live);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_parse_errors_without_ending() {
+        let messages = stream::iter(vec![
+            Ok(Message::Text("{ not json".into())),
+            Ok(Message::Ping(Vec::new().into())),
+            Ok(Message::Text(
+                r#"{"type":"summary","symbol":"SPY","open":"282.42","prevClose":"281.75"}"#.into(),
+            )),
+            Ok(Message::Close(None)),
+            Ok(Message::Text("ignored after close".into())),
+        ]);
+
+        let events: Vec<_> = MarketEventStream::from_websocket(messages, None)
+            .collect()
+            .await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Err(Error::StreamEventParseError(_, _))));
+        assert!(matches!(events[1], Ok(MarketEvent::Summary(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_transport_error() {
+        let messages = stream::iter(vec![
+            Err(tungstenite::Error::ConnectionClosed),
+            Ok(Message::Text("ignored after error".into())),
+        ]);
+
+        let events: Vec<_> = MarketEventStream::from_websocket(messages, None)
+            .collect()
+            .await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(Error::WebSocketError(_))));
     }
 
     #[test]
@@ -522,4 +1095,459 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_market_session_payload_form_params() {
+        let symbols = ["AAPL".to_string(), "SPY".to_string()];
+        let payload = MarketSessionPayload::builder()
+            .symbols(&symbols)
+            .filters(&[MarketSessionFilter::QUOTE, MarketSessionFilter::TRADE])
+            .session_id("session-12345")
+            .valid_only(true)
+            .build();
+
+        assert_eq!(
+            payload.form_params(),
+            vec![
+                ("symbols", "AAPL,SPY".to_owned()),
+                ("sessionid", "session-12345".to_owned()),
+                ("linebreak", "true".to_owned()),
+                ("filter", "quote,trade".to_owned()),
+                ("validOnly", "true".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_over_http() {
+        let mut server = Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"stream":{"url":"https://stream.tradier.com/v1/markets/events","sessionid":"http-session"}}"#)
+            .create_async()
+            .await;
+        let stream_mock = server
+            .mock("POST", "/v1/markets/events")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("symbols".into(), "SPY,C".into()),
+                mockito::Matcher::UrlEncoded("sessionid".into(), "http-session".into()),
+                mockito::Matcher::UrlEncoded("linebreak".into(), "true".into()),
+            ]))
+            .with_status(200)
+            .with_chunked_body(|writer| {
+                writer.write_all(b"{\"type\":\"summary\",\"symbol\":\"SPY\",\"open\":\"282.4")?;
+                writer.flush()?;
+                writer.write_all(b"2\"}\n{\"type\":\"summary\",\"symbol\":\"C\"}\n")
+            })
+            .create_async()
+            .await;
+
+        let config = create_test_config()
+            .server_url(&server.url())
+            .http_base_url(&server.url())
+            .web_socket_path("/v1/markets/events")
+            .transport(StreamTransport::Http)
+            .finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        assert_eq!(market_session.transport(), StreamTransport::Http);
+        assert_eq!(
+            market_session.get_http_url(),
+            format!("{}/v1/markets/events", server.url())
+        );
+
+        let symbols = ["SPY".to_owned(), "C".to_owned()];
+        let events: Vec<_> = market_session
+            .stream(
+                MarketSessionPayload::builder()
+                    .session_id(market_session.get_session_id())
+                    .symbols(&symbols)
+                    .build(),
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        stream_mock.assert_async().await;
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            Ok(MarketEvent::Summary(summary)) => assert_eq!(summary.open, Some(282.42)),
+            other => panic!("Expected a summary event, got {:?}", other),
+        }
+        assert_eq!(events[1].as_ref().unwrap().symbol(), "C");
+    }
+
+    #[tokio::test]
+    async fn test_http_stream_reports_error_status() {
+        let mut server = Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(r#"{"stream":{"url":"https://stream.tradier.com/v1/markets/events","sessionid":"expired"}}"#)
+            .create_async()
+            .await;
+        let _stream_mock = server
+            .mock("POST", "/v1/markets/events")
+            .with_status(400)
+            .with_body(r#"{"errors":{"error":"Session not found"}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config()
+            .server_url(&server.url())
+            .http_base_url(&server.url())
+            .web_socket_path("/v1/markets/events")
+            .finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let symbols = ["SPY".to_owned()];
+        let result = market_session
+            .http_stream(MarketSessionPayload::recommended(&symbols, "expired"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::ApiError(reqwest::StatusCode::BAD_REQUEST, messages)) if messages == ["Session not found"]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_stream_resubscribes_after_close() {
+        let ws_address = ("127.0.0.1", 9997u16);
+        let mut server = Server::new_async().await;
+        let session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://{}:{}/v1/markets/events","sessionid":"reconnect"}}}}"#,
+                ws_address.0, ws_address.1
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let listener = tokio::net::TcpListener::bind(ws_address).await.unwrap();
+        tokio::spawn(async move {
+            for _ in 0..2 {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                let Some(Ok(Message::Text(subscription))) = websocket.next().await else {
+                    panic!("expected a subscription");
+                };
+                assert!(subscription.contains(r#""sessionid":"reconnect""#));
+                websocket
+                    .send(Message::Text(
+                        r#"{"type":"summary","symbol":"SPY","open":"282.42"}"#.into(),
+                    ))
+                    .await
+                    .unwrap();
+                websocket.close(None).await.unwrap();
+            }
+        });
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let symbols = ["SPY".to_owned()];
+        let session_id = market_session.get_session_id().to_owned();
+        let payload = MarketSessionPayload::builder()
+            .session_id(&session_id)
+            .symbols(&symbols)
+            .build();
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(std::time::Duration::from_millis(1))
+            .jitter(0.0)
+            .build();
+        let items: Vec<_> = market_session
+            .reconnecting_stream(&config, payload, policy)
+            .await
+            .unwrap()
+            .take(6)
+            .collect()
+            .await;
+
+        use crate::wssession::StreamEvent;
+        assert!(matches!(
+            items[0],
+            Ok(StreamEvent::Event(MarketEvent::Summary(_)))
+        ));
+        assert!(matches!(items[1], Ok(StreamEvent::Disconnected(None))));
+        assert!(matches!(
+            items[2],
+            Ok(StreamEvent::Reconnecting { attempt: 1, .. })
+        ));
+        assert!(matches!(
+            items[3],
+            Ok(StreamEvent::Reconnected {
+                attempt: 1,
+                session_renewed: false
+            })
+        ));
+        assert!(matches!(
+            items[4],
+            Ok(StreamEvent::Event(MarketEvent::Summary(_)))
+        ));
+        assert!(matches!(items[5], Ok(StreamEvent::Disconnected(None))));
+        session_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_stream_backs_off_when_the_server_closes_right_away() {
+        let ws_address = ("127.0.0.1", 9993u16);
+        let mut server = Server::new_async().await;
+        let session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://{}:{}/v1/markets/events","sessionid":"flapping"}}}}"#,
+                ws_address.0, ws_address.1
+            ))
+            .expect(3)
+            .create_async()
+            .await;
+
+        let listener = tokio::net::TcpListener::bind(ws_address).await.unwrap();
+        tokio::spawn(async move {
+            for _ in 0..4 {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                let Some(Ok(Message::Text(_))) = websocket.next().await else {
+                    panic!("expected a subscription");
+                };
+                websocket.close(None).await.unwrap();
+            }
+        });
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let symbols = ["SPY".to_owned()];
+        let session_id = market_session.get_session_id().to_owned();
+        let payload = MarketSessionPayload::builder()
+            .session_id(&session_id)
+            .symbols(&symbols)
+            .build();
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(std::time::Duration::from_millis(1))
+            .jitter(0.0)
+            .build();
+        let items: Vec<_> = market_session
+            .reconnecting_stream(&config, payload, policy)
+            .await
+            .unwrap()
+            .take(9)
+            .collect()
+            .await;
+
+        use crate::wssession::StreamEvent;
+        let attempts: Vec<u32> = items
+            .iter()
+            .filter_map(|item| match item {
+                Ok(StreamEvent::Reconnecting { attempt, .. }) => Some(*attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attempts, vec![1, 2, 3]);
+        assert!(matches!(
+            items[8],
+            Ok(StreamEvent::Reconnected {
+                attempt: 3,
+                session_renewed: true
+            })
+        ));
+        session_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_subscription_handle_updates_live_stream_and_reconnects() {
+        let ws_address = ("127.0.0.1", 9996u16);
+        let mut server = Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"stream":{{"url":"ws://{}:{}/v1/markets/events","sessionid":"live"}}}}"#,
+                ws_address.0, ws_address.1
+            ))
+            .create_async()
+            .await;
+
+        let listener = tokio::net::TcpListener::bind(ws_address).await.unwrap();
+        let server_task = tokio::spawn(async move {
+            let mut received = vec![];
+            let summary = |symbol: &str| {
+                Message::Text(format!(r#"{{"type":"summary","symbol":"{symbol}"}}"#).into())
+            };
+
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            for symbol in ["SPY", "AAPL"] {
+                let Some(Ok(Message::Text(payload))) = websocket.next().await else {
+                    panic!("expected a subscription");
+                };
+                received.push(payload.to_string());
+                websocket.send(summary(symbol)).await.unwrap();
+            }
+            websocket.close(None).await.unwrap();
+
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let Some(Ok(Message::Text(payload))) = websocket.next().await else {
+                panic!("expected a subscription");
+            };
+            received.push(payload.to_string());
+            websocket.send(summary("C")).await.unwrap();
+            received
+        });
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let symbols = ["SPY".to_owned()];
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(std::time::Duration::from_millis(1))
+            .jitter(0.0)
+            .build();
+        let (events, handle) = market_session
+            .subscribe(
+                &config,
+                MarketSessionPayload::recommended(&symbols, "stale"),
+                policy,
+            )
+            .await
+            .unwrap();
+        let symbols = events.filter_map(|item| {
+            ready(match item {
+                Ok(crate::wssession::StreamEvent::Event(event)) => Some(event.symbol().to_owned()),
+                _ => None,
+            })
+        });
+        let mut symbols = std::pin::pin!(symbols);
+
+        assert_eq!(symbols.next().await.unwrap(), "SPY");
+        handle.add_symbols(["AAPL"]);
+        assert_eq!(symbols.next().await.unwrap(), "AAPL");
+        assert_eq!(symbols.next().await.unwrap(), "C");
+        assert_eq!(handle.symbols(), vec!["SPY", "AAPL"]);
+
+        let received = server_task.await.unwrap();
+        assert!(received[0].contains(r#""symbols":["SPY"]"#));
+        assert!(received[0].contains(r#""sessionid":"live""#));
+        assert!(received[1].contains(r#""symbols":["SPY","AAPL"]"#));
+        assert!(received[2].contains(r#""symbols":["SPY","AAPL"]"#));
+    }
+
+    /// Reads one HTTP request from `tcp` and returns its body.
+    async fn read_http_request(tcp: &mut tokio::net::TcpStream) -> String {
+        use tokio::io::AsyncReadExt;
+
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        loop {
+            let read = tcp.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).into_owned();
+            let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                continue;
+            };
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0usize);
+            if body.len() >= length {
+                return body.to_owned();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_subscription_update_restarts_the_request_without_reconnecting() {
+        use tokio::io::AsyncWriteExt;
+
+        let http_address = ("127.0.0.1", 9992u16);
+        let mut server = Server::new_async().await;
+        let _session_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(r#"{"stream":{"url":"https://stream.tradier.com/v1/markets/events","sessionid":"http-live"}}"#)
+            .create_async()
+            .await;
+
+        let listener = tokio::net::TcpListener::bind(http_address).await.unwrap();
+        let server_task = tokio::spawn(async move {
+            let chunk = |line: &str| format!("{:x}\r\n{line}\n\r\n", line.len() + 1);
+            let head = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ntransfer-encoding: chunked\r\n\r\n";
+
+            let (mut first, _) = listener.accept().await.unwrap();
+            let first_body = read_http_request(&mut first).await;
+            let response = head.to_owned() + &chunk(r#"{"type":"summary","symbol":"SPY"}"#);
+            first.write_all(response.as_bytes()).await.unwrap();
+
+            let (mut second, _) = listener.accept().await.unwrap();
+            let second_body = read_http_request(&mut second).await;
+            let response =
+                head.to_owned() + &chunk(r#"{"type":"summary","symbol":"AAPL"}"#) + "0\r\n\r\n";
+            second.write_all(response.as_bytes()).await.unwrap();
+            drop(first);
+            (first_body, second_body)
+        });
+
+        let config = create_test_config()
+            .server_url(&server.url())
+            .http_base_url(&format!("http://{}:{}", http_address.0, http_address.1))
+            .web_socket_path("/v1/markets/events")
+            .transport(StreamTransport::Http)
+            .finish();
+        let session_manager = SessionManager::default();
+        let market_session = MarketSession::new_with_session_manager(&config, &session_manager)
+            .await
+            .unwrap();
+        let symbols = ["SPY".to_owned()];
+        let (events, handle) = market_session
+            .subscribe(
+                &config,
+                MarketSessionPayload::recommended(&symbols, ""),
+                ReconnectPolicy::default(),
+            )
+            .await
+            .unwrap();
+        let mut events = std::pin::pin!(events);
+
+        use crate::wssession::StreamEvent;
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(StreamEvent::Event(event))) if event.symbol() == "SPY"
+        ));
+        handle.add_symbols(["AAPL"]);
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(StreamEvent::Event(event))) if event.symbol() == "AAPL"
+        ));
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(StreamEvent::Disconnected(None)))
+        ));
+
+        let (first_body, second_body) = server_task.await.unwrap();
+        assert!(first_body.contains("symbols=SPY&"));
+        assert!(first_body.contains("sessionid=http-live"));
+        assert!(second_body.contains("symbols=SPY%2CAAPL&"));
+    }
 }
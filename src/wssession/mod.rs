@@ -8,12 +8,22 @@
 //!
 //! ## Overview
 //!
-//! - **`AccountSession`**: Manages WebSocket sessions for streaming account-related events, such as
-//!   order status updates and balance changes.
-//! - **`MarketSession`**: Handles WebSocket sessions for streaming market data, including real-time
-//!   quotes and trades.
-//! - **`SessionManager`**: Ensures that only one streaming session is active at any given time, adhering
-//!   to Tradier's limitation of a single concurrent session per user.
+//! - **`AccountSession`**: Manages WebSocket sessions for streaming account-related events,
+//!   delivered as a stream of typed `OrderEvent` values.
+//! - **`MarketSession`**: Handles sessions for streaming market data, including real-time
+//!   quotes and trades, delivered as a stream of typed `MarketEvent` values. Events are received
+//!   over a WebSocket or, where outbound WebSockets are blocked, over HTTP chunked streaming; the
+//!   transport is selected by `StreamTransport` in the streaming configuration.
+//! - **`ReconnectingStream`**: Wraps a market or account stream, renewing the session and
+//!   resubscribing with exponential backoff when the connection drops, and reporting each step
+//!   as a `StreamEvent`.
+//! - **`SubscriptionHandle`**: Changes the symbols and filters of a running market stream without
+//!   tearing down the connection.
+//! - **`FrameRecorder`** and **`FrameReplay`**: Record the raw frames of a market session to a
+//!   file and replay them, at their original pace or faster, as the same typed events.
+//! - **`SessionManager`**: Ensures that at most one market and one account session are active at
+//!   any given time, adhering to Tradier's limit of one concurrent session of each type per user.
+//!   A session releases its slot when it is dropped.
 //!
 //! ## Usage
 //!
@@ -22,10 +32,18 @@
 
 mod account;
 
+mod events;
 mod market;
+mod reconnect;
+mod recording;
+mod subscription;
 
 pub(crate) mod session;
 pub(crate) mod session_manager;
 
-pub use account::AccountSession;
-pub use market::{MarketSession, MarketSessionFilter, MarketSessionPayload};
+pub use account::{AccountEventStream, AccountSession, AccountSessionPayload};
+pub use events::{MarketEvent, OrderEvent, Quote, Summary, Timesale, Trade};
+pub use market::{MarketEventStream, MarketSession, MarketSessionFilter, MarketSessionPayload};
+pub use reconnect::{ReconnectPolicy, ReconnectingStream, StreamEvent};
+pub use recording::{FrameRecorder, FrameReplay, RecordedFrame, ReplaySpeed};
+pub use subscription::SubscriptionHandle;
@@ -0,0 +1,577 @@
+//! # Reconnecting Streams
+//!
+//! Tradier drops streaming connections routinely and expires session ids that have been unused
+//! for five minutes. A [`ReconnectingStream`] hides both: when the connection is lost it waits
+//! according to a [`ReconnectPolicy`], renews the session if needed, and sends the same
+//! subscription again. Every step is reported as a [`StreamEvent`] so consumers can tell where
+//! events may have been missed.
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::future::ready;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::streaming::{BackpressurePolicy, BufferedStream, Conflate};
+use crate::{Config, Error, Result};
+
+/// An item of a [`ReconnectingStream`]: either an event received from Tradier, or a change in
+/// the state of the connection.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StreamEvent<T> {
+    /// An event received from Tradier.
+    Event(T),
+    /// The connection was lost, either because of the given error or because it was closed.
+    /// Events sent until the stream is [`Reconnected`](StreamEvent::Reconnected) are missed.
+    Disconnected(Option<Error>),
+    /// A reconnection attempt will be made after `delay`.
+    Reconnecting { attempt: u32, delay: Duration },
+    /// The subscription was sent again. `session_renewed` is set when a new session had to be
+    /// created for it.
+    Reconnected { attempt: u32, session_renewed: bool },
+}
+
+/// Exponential backoff used between reconnection attempts.
+///
+/// The delay before attempt `n` is `initial_delay * multiplier^(n - 1)`, capped at `max_delay`,
+/// and then moved randomly by up to `jitter` (a fraction of the delay) in either direction so
+/// that clients do not reconnect in lockstep.
+///
+/// A connection only counts as successful once it delivered an event or stayed up for
+/// `min_uptime`; a server that accepts the subscription and closes right away keeps the
+/// attempt count, and therefore the delay, growing.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use tradier::wssession::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy::builder()
+///     .initial_delay(Duration::from_secs(1))
+///     .max_delay(Duration::from_secs(30))
+///     .jitter(0.0)
+///     .build();
+/// assert_eq!(policy.delay(3), Duration::from_secs(4));
+/// assert_eq!(policy.delay(10), Duration::from_secs(30));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: f64,
+    min_uptime: Duration,
+    max_attempts: Option<u32>,
+}
+
+#[bon::bon]
+impl ReconnectPolicy {
+    /// Constructs a new `ReconnectPolicy`.
+    ///
+    /// # Arguments
+    /// - `initial_delay`: Delay before the first attempt. Defaults to 5 seconds.
+    /// - `max_delay`: Upper bound of the delay before jitter. Defaults to 60 seconds.
+    /// - `multiplier`: Growth factor of the delay between attempts. Defaults to 2.
+    /// - `jitter`: Fraction of the delay, between 0 and 1, by which it is randomized.
+    ///   Defaults to 0.2.
+    /// - `min_uptime`: Time after which a connection that delivered no event resets the attempt
+    ///   count. Defaults to 10 seconds.
+    /// - `max_attempts`: Number of consecutive failed attempts after which the stream gives up.
+    ///   Unlimited by default.
+    #[builder(builder_type(vis = "pub"))]
+    fn new(
+        #[builder(default = Duration::from_secs(5))] initial_delay: Duration,
+        #[builder(default = Duration::from_secs(60))] max_delay: Duration,
+        #[builder(default = 2.0)] multiplier: f64,
+        #[builder(default = 0.2)] jitter: f64,
+        #[builder(default = Duration::from_secs(10))] min_uptime: Duration,
+        max_attempts: Option<u32>,
+    ) -> Self {
+        ReconnectPolicy {
+            initial_delay,
+            max_delay: max_delay.max(initial_delay),
+            multiplier: multiplier.max(1.0),
+            jitter: jitter.clamp(0.0, 1.0),
+            min_uptime,
+            max_attempts,
+        }
+    }
+
+    /// Builds the default policy, starting from the `reconnect_interval` (in seconds) of the
+    /// streaming configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self::builder()
+            .initial_delay(Duration::from_secs(config.streaming.reconnect_interval))
+            .build()
+    }
+
+    /// Returns the delay to wait before reconnection attempt `attempt`, counting from 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = (self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_delay.as_secs_f64());
+        let spread = self.jitter * (2.0 * random_fraction() - 1.0);
+        Duration::from_secs_f64((delay * (1.0 + spread)).max(0.0))
+    }
+
+    fn gives_up_after(&self, attempt: u32) -> bool {
+        self.max_attempts.is_some_and(|max| attempt >= max)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Returns a random number in `[0, 1)`, seeded by the per-instance keys of [`RandomState`].
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A subscription that can be opened again, implemented for market and account sessions.
+#[async_trait::async_trait]
+pub(crate) trait Resubscribe: Send {
+    type Event: Send + 'static;
+
+    /// Sends the subscription, first creating a new session if `renew_session` is set or the
+    /// current one expired. Returns the events of the new connection and whether the session
+    /// was renewed.
+    async fn resubscribe(
+        &mut self,
+        renew_session: bool,
+    ) -> Result<(BoxStream<'static, Result<Self::Event>>, bool)>;
+
+    /// Records that the connection to the session was lost just now.
+    fn disconnected(&mut self);
+}
+
+/// Shared state of a [`Resubscribe`] implementation, holding the configuration used to renew
+/// the session and the payload to send again.
+pub(crate) struct Subscription<S, P> {
+    pub(crate) session: S,
+    pub(crate) config: Config,
+    pub(crate) payload: P,
+}
+
+enum Phase<T> {
+    /// `attempt` is the reconnection attempt that opened `events`, or zero once the connection
+    /// proved to work.
+    Streaming {
+        events: BoxStream<'static, Result<T>>,
+        attempt: u32,
+        since: Instant,
+    },
+    Waiting {
+        attempt: u32,
+    },
+    /// `attempt` is zero when opening the first connection.
+    Connecting {
+        attempt: u32,
+        delay: Duration,
+    },
+    Done,
+}
+
+/// A stream of typed events that survives dropped connections and expired sessions.
+///
+/// Payloads that cannot be parsed are yielded as [`Error::StreamEventParseError`] without
+/// interrupting the stream, as are the errors of failed reconnection attempts. The stream only
+/// ends once the [`ReconnectPolicy`] gives up, after yielding the last error or disconnection.
+pub struct ReconnectingStream<'a, T> {
+    inner: BoxStream<'a, Result<StreamEvent<T>>>,
+}
+
+impl<'a, T: Send + 'static> ReconnectingStream<'a, T> {
+    pub(crate) fn new<R>(
+        subscription: R,
+        events: BoxStream<'static, Result<T>>,
+        policy: ReconnectPolicy,
+    ) -> Self
+    where
+        R: Resubscribe<Event = T> + 'a,
+    {
+        let phase = Phase::Streaming {
+            events,
+            attempt: 0,
+            since: Instant::now(),
+        };
+        Self::from_phase(subscription, policy, phase)
+    }
+
+    /// Like [`new`](ReconnectingStream::new), but opens the first connection from within the
+    /// stream, so that a failure is retried under `policy` like a lost connection instead of
+    /// being returned before the stream exists.
+    pub(crate) fn connecting<R>(subscription: R, policy: ReconnectPolicy) -> Self
+    where
+        R: Resubscribe<Event = T> + 'a,
+    {
+        let phase = Phase::Connecting {
+            attempt: 0,
+            delay: Duration::ZERO,
+        };
+        Self::from_phase(subscription, policy, phase)
+    }
+
+    fn from_phase<R>(subscription: R, policy: ReconnectPolicy, phase: Phase<T>) -> Self
+    where
+        R: Resubscribe<Event = T> + 'a,
+    {
+        let state = (subscription, policy, phase);
+        let inner = stream::unfold(state, |(mut subscription, policy, phase)| async move {
+            let (item, phase) = match phase {
+                Phase::Streaming {
+                    mut events,
+                    attempt,
+                    since,
+                } => match events.next().await {
+                    Some(Ok(event)) => (
+                        Some(Ok(StreamEvent::Event(event))),
+                        Phase::Streaming {
+                            events,
+                            attempt: 0,
+                            since,
+                        },
+                    ),
+                    Some(Err(e @ Error::StreamEventParseError(..))) => (
+                        Some(Err(e)),
+                        Phase::Streaming {
+                            events,
+                            attempt,
+                            since,
+                        },
+                    ),
+                    next => {
+                        let error = match next {
+                            Some(Err(e)) => {
+                                warn!("Connection lost: {}", e);
+                                Some(e)
+                            }
+                            _ => {
+                                info!("Connection closed");
+                                None
+                            }
+                        };
+                        subscription.disconnected();
+                        let attempt = if since.elapsed() >= policy.min_uptime {
+                            0
+                        } else {
+                            attempt
+                        };
+                        let phase = if attempt > 0 && policy.gives_up_after(attempt) {
+                            Phase::Done
+                        } else {
+                            Phase::Waiting {
+                                attempt: attempt + 1,
+                            }
+                        };
+                        (Some(Ok(StreamEvent::Disconnected(error))), phase)
+                    }
+                },
+                Phase::Waiting { attempt } => {
+                    let delay = policy.delay(attempt);
+                    info!("Reconnecting in {:?} (attempt {})", delay, attempt);
+                    (
+                        Some(Ok(StreamEvent::Reconnecting { attempt, delay })),
+                        Phase::Connecting { attempt, delay },
+                    )
+                }
+                Phase::Connecting { attempt, delay } => {
+                    tokio::time::sleep(delay).await;
+                    match subscription.resubscribe(attempt > 1).await {
+                        Ok((events, session_renewed)) => (
+                            // The first connection is not a reconnection.
+                            (attempt > 0).then_some(Ok(StreamEvent::Reconnected {
+                                attempt,
+                                session_renewed,
+                            })),
+                            Phase::Streaming {
+                                events,
+                                attempt,
+                                since: Instant::now(),
+                            },
+                        ),
+                        Err(e) if policy.gives_up_after(attempt) => (Some(Err(e)), Phase::Done),
+                        Err(e) => (
+                            Some(Err(e)),
+                            Phase::Waiting {
+                                attempt: attempt + 1,
+                            },
+                        ),
+                    }
+                }
+                Phase::Done => return None,
+            };
+            Some((item, (subscription, policy, phase)))
+        });
+        ReconnectingStream {
+            inner: inner.filter_map(ready).boxed(),
+        }
+    }
+}
+
+impl<T: Conflate + Send + 'static> ReconnectingStream<'static, T> {
+    /// Reads the stream ahead into a buffer of `capacity` items that applies `policy` when the
+    /// consumer falls behind. Lifecycle events and errors are always delivered. Must be called
+    /// from within a Tokio runtime.
+    pub fn with_backpressure(
+        self,
+        capacity: usize,
+        policy: BackpressurePolicy,
+    ) -> BufferedStream<Result<StreamEvent<T>>> {
+        BufferedStream::new(self, capacity, policy)
+    }
+}
+
+impl<T> Stream for ReconnectingStream<'_, T> {
+    type Item = Result<StreamEvent<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    struct FakeSubscription {
+        connections: VecDeque<Result<Vec<Result<u32>>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Resubscribe for FakeSubscription {
+        type Event = u32;
+
+        async fn resubscribe(
+            &mut self,
+            renew_session: bool,
+        ) -> Result<(BoxStream<'static, Result<u32>>, bool)> {
+            let events = self
+                .connections
+                .pop_front()
+                .expect("an expected connection")?;
+            Ok((stream::iter(events).boxed(), renew_session))
+        }
+
+        fn disconnected(&mut self) {}
+    }
+
+    fn quick_policy(max_attempts: Option<u32>) -> ReconnectPolicy {
+        ReconnectPolicy::builder()
+            .initial_delay(Duration::from_millis(1))
+            .max_delay(Duration::from_millis(4))
+            .jitter(0.0)
+            .maybe_max_attempts(max_attempts)
+            .build()
+    }
+
+    fn parse_error() -> Error {
+        Error::StreamEventParseError(
+            "{".to_owned(),
+            serde_json::from_str::<u32>("{").unwrap_err(),
+        )
+    }
+
+    #[test]
+    fn test_delay_grows_exponentially_up_to_the_maximum() {
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(Duration::from_secs(2))
+            .max_delay(Duration::from_secs(10))
+            .jitter(0.0)
+            .build();
+        let delays: Vec<_> = (1..=5)
+            .map(|attempt| policy.delay(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, vec![2, 4, 8, 10, 10]);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(Duration::from_secs(10))
+            .jitter(0.5)
+            .build();
+        for _ in 0..100 {
+            let delay = policy.delay(1);
+            assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(15));
+        }
+    }
+
+    #[test]
+    fn test_from_config_uses_reconnect_interval() {
+        let mut config = crate::utils::tests::create_test_config()
+            .server_url("")
+            .finish();
+        config.streaming.reconnect_interval = 7;
+        let policy = ReconnectPolicy::from_config(&config);
+        assert_eq!(policy.initial_delay, Duration::from_secs(7));
+        assert_eq!(policy.max_attempts, None);
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_close_and_error() {
+        let subscription = FakeSubscription {
+            connections: VecDeque::from(vec![
+                Err(Error::UnexpectedError("refused".to_owned())),
+                Ok(vec![Ok(3)]),
+            ]),
+        };
+        let initial = stream::iter(vec![
+            Ok(1),
+            Err(parse_error()),
+            Ok(2),
+            Err(Error::UnexpectedError("reset".to_owned())),
+        ])
+        .boxed();
+        let items: Vec<_> = ReconnectingStream::new(subscription, initial, quick_policy(Some(3)))
+            .take(10)
+            .collect()
+            .await;
+
+        assert!(matches!(items[0], Ok(StreamEvent::Event(1))));
+        assert!(matches!(items[1], Err(Error::StreamEventParseError(..))));
+        assert!(matches!(items[2], Ok(StreamEvent::Event(2))));
+        assert!(matches!(
+            items[3],
+            Ok(StreamEvent::Disconnected(Some(Error::UnexpectedError(_))))
+        ));
+        assert!(matches!(
+            items[4],
+            Ok(StreamEvent::Reconnecting { attempt: 1, .. })
+        ));
+        assert!(matches!(items[5], Err(Error::UnexpectedError(_))));
+        assert!(matches!(
+            items[6],
+            Ok(StreamEvent::Reconnecting { attempt: 2, delay }) if delay == Duration::from_millis(2)
+        ));
+        assert!(matches!(
+            items[7],
+            Ok(StreamEvent::Reconnected {
+                attempt: 2,
+                session_renewed: true
+            })
+        ));
+        assert!(matches!(items[8], Ok(StreamEvent::Event(3))));
+        assert!(matches!(items[9], Ok(StreamEvent::Disconnected(None))));
+    }
+
+    fn parse_number(frame: &str) -> Vec<Result<u32>> {
+        vec![serde_json::from_str(frame)
+            .map_err(|e| Error::StreamEventParseError(frame.to_owned(), e))]
+    }
+
+    #[tokio::test]
+    async fn test_frames_that_are_not_utf8_do_not_reconnect() {
+        use tokio_tungstenite::tungstenite::Message;
+
+        let subscription = FakeSubscription {
+            connections: VecDeque::new(),
+        };
+        let frames = stream::iter(vec![
+            Ok(Message::Text("1".into())),
+            Ok(Message::Binary(vec![0xff, 0xfe].into())),
+            Ok(Message::Text("2".into())),
+        ]);
+        let initial = crate::wssession::events::websocket_events(frames, parse_number);
+        let items: Vec<_> = ReconnectingStream::new(subscription, initial, quick_policy(Some(1)))
+            .take(3)
+            .collect()
+            .await;
+
+        assert!(matches!(items[0], Ok(StreamEvent::Event(1))));
+        assert!(matches!(items[1], Err(Error::StreamEventParseError(..))));
+        assert!(matches!(items[2], Ok(StreamEvent::Event(2))));
+    }
+
+    #[tokio::test]
+    async fn test_connections_closed_right_away_keep_backing_off() {
+        let subscription = FakeSubscription {
+            connections: VecDeque::from(vec![Ok(vec![]), Ok(vec![]), Ok(vec![Ok(1)]), Ok(vec![])]),
+        };
+        let items: Vec<_> =
+            ReconnectingStream::new(subscription, stream::empty().boxed(), quick_policy(None))
+                .take(14)
+                .collect()
+                .await;
+        let attempts: Vec<u32> = items
+            .iter()
+            .filter_map(|item| match item {
+                Ok(StreamEvent::Reconnecting { attempt, .. }) => Some(*attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attempts, vec![1, 2, 3, 1]);
+        assert!(matches!(items[9], Ok(StreamEvent::Event(1))));
+    }
+
+    #[tokio::test]
+    async fn test_connections_up_for_the_minimum_time_reset_the_attempts() {
+        let subscription = FakeSubscription {
+            connections: VecDeque::from(vec![Ok(vec![]), Ok(vec![])]),
+        };
+        let policy = ReconnectPolicy::builder()
+            .initial_delay(Duration::from_millis(1))
+            .jitter(0.0)
+            .min_uptime(Duration::ZERO)
+            .build();
+        let items: Vec<_> = ReconnectingStream::new(subscription, stream::empty().boxed(), policy)
+            .take(7)
+            .collect()
+            .await;
+        assert!(matches!(
+            items[4],
+            Ok(StreamEvent::Reconnecting { attempt: 1, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_connections_keep_closing() {
+        let subscription = FakeSubscription {
+            connections: VecDeque::from(vec![Ok(vec![]), Ok(vec![])]),
+        };
+        let items: Vec<_> =
+            ReconnectingStream::new(subscription, stream::empty().boxed(), quick_policy(Some(2)))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 7);
+        assert!(matches!(
+            items[5],
+            Ok(StreamEvent::Reconnected { attempt: 2, .. })
+        ));
+        assert!(matches!(items[6], Ok(StreamEvent::Disconnected(None))));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let subscription = FakeSubscription {
+            connections: VecDeque::from(vec![
+                Err(Error::UnexpectedError("first".to_owned())),
+                Err(Error::UnexpectedError("second".to_owned())),
+            ]),
+        };
+        let items: Vec<_> =
+            ReconnectingStream::new(subscription, stream::empty().boxed(), quick_policy(Some(2)))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 5);
+        assert!(matches!(items[0], Ok(StreamEvent::Disconnected(None))));
+        assert!(matches!(
+            &items[4],
+            Err(Error::UnexpectedError(message)) if message == "second"
+        ));
+    }
+}
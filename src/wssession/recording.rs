@@ -0,0 +1,480 @@
+//! # Recording and Replay
+//!
+//! A [`FrameRecorder`] appends the raw frames a [`MarketSession`](super::MarketSession)
+//! receives over its WebSocket to a file, together with the time each one was received. A
+//! [`FrameReplay`] reads such a file back and feeds its frames through the same parser as a live
+//! session, so recorded sessions can be used for debugging and backtests.
+//!
+//! Each record is one line: the length in bytes of a JSON object, a space, the object itself and
+//! a line break. `t` is the receive time in microseconds since the epoch and `f` the frame as
+//! received:
+//!
+//! ```text
+//! 70 {"t":1557757189000000,"f":"{\"type\":\"summary\",\"symbol\":\"SPY\"}"}
+//! ```
+//!
+//! The length prefix lets a reader detect a record truncated by a crash while it was being
+//! appended.
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{sleep_until, Instant};
+use tracing::error;
+
+use super::MarketEventStream;
+use crate::{Error, Result};
+
+/// A frame received by a market session, as stored by a [`FrameRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedFrame {
+    /// When the frame was received.
+    pub received: DateTime<Utc>,
+    /// The frame as received.
+    pub frame: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record<'a> {
+    #[serde(rename = "t", with = "chrono::serde::ts_microseconds")]
+    received: DateTime<Utc>,
+    #[serde(rename = "f", borrow)]
+    frame: Cow<'a, str>,
+}
+
+enum Command {
+    Frame(Record<'static>),
+    Flush(oneshot::Sender<std::io::Result<()>>),
+}
+
+/// Number of frames a [`FrameRecorder`] buffers by default while the file is being written.
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// Appends received frames to a recording file.
+///
+/// Frames are written by a background task through a bounded buffer, so recording never slows
+/// down the stream: when the file cannot keep up and the buffer is full, frames are dropped and
+/// counted in [`dropped_frames`](FrameRecorder::dropped_frames). Clones write to the same file,
+/// which is closed once every clone is dropped. Attach a recorder to a session with
+/// [`MarketSession::set_recorder`](super::MarketSession::set_recorder).
+#[derive(Debug, Clone)]
+pub struct FrameRecorder {
+    commands: mpsc::Sender<Command>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl FrameRecorder {
+    /// Opens `path` for appending, creating it if needed, and spawns the task that writes to it.
+    /// Buffers up to 4096 frames. Must be called from within a Tokio runtime.
+    pub async fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_capacity(path, DEFAULT_CAPACITY).await
+    }
+
+    /// Like [`create`](FrameRecorder::create), but buffers up to `capacity` frames, at least one.
+    pub async fn with_capacity(path: impl AsRef<Path>, capacity: usize) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        let (commands, receiver) = mpsc::channel(capacity.max(1));
+        tokio::spawn(write_records(BufWriter::new(file), receiver));
+        Ok(FrameRecorder {
+            commands,
+            dropped: Arc::default(),
+        })
+    }
+
+    /// Returns how many frames were dropped, across all clones, because the buffer was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Records `frame` as received now.
+    pub fn record(&self, frame: &str) {
+        self.record_at(Utc::now(), frame);
+    }
+
+    /// Records `frame` as received at `received`.
+    pub fn record_at(&self, received: DateTime<Utc>, frame: &str) {
+        let record = Record {
+            received,
+            frame: Cow::Owned(frame.to_owned()),
+        };
+        match self.commands.try_send(Command::Frame(record)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            // The writer only stops after a write error, which the next flush reports.
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+
+    /// Waits until every frame recorded so far has been written to the file.
+    ///
+    /// # Returns
+    /// - `Err(Error::IoError)`: If writing to the file failed. Frames recorded after a failure
+    ///   are discarded.
+    pub async fn flush(&self) -> Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        let stopped = || std::io::Error::other("the recording was stopped by a write error");
+        self.commands
+            .send(Command::Flush(sender))
+            .await
+            .map_err(|_| stopped())?;
+        Ok(receiver.await.map_err(|_| stopped())??)
+    }
+}
+
+/// Writes the recorded frames in batches, flushing the file after each batch. Stops at the first
+/// write error, which is reported to pending and later flushes.
+async fn write_records(mut file: BufWriter<File>, mut commands: mpsc::Receiver<Command>) {
+    while let Some(command) = commands.recv().await {
+        let mut batch = vec![command];
+        while let Ok(command) = commands.try_recv() {
+            batch.push(command);
+        }
+        let mut result = Ok(());
+        let mut flushes = vec![];
+        for command in batch {
+            match command {
+                Command::Frame(record) if result.is_ok() => {
+                    result = write_record(&mut file, &record).await;
+                }
+                Command::Frame(_) => {}
+                Command::Flush(done) => flushes.push(done),
+            }
+        }
+        if result.is_ok() {
+            result = file.flush().await;
+        }
+        for done in flushes {
+            let reply = match &result {
+                Ok(()) => Ok(()),
+                Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
+            };
+            let _ = done.send(reply);
+        }
+        if let Err(e) = result {
+            error!("Unable to write recording: {}", e);
+            return;
+        }
+    }
+}
+
+async fn write_record(file: &mut BufWriter<File>, record: &Record<'_>) -> std::io::Result<()> {
+    let json = serde_json::to_string(record)?;
+    file.write_all(format!("{} {}\n", json.len(), json).as_bytes())
+        .await
+}
+
+/// How fast a [`FrameReplay`] delivers frames.
+///
+/// Variants:
+/// - `Original`: With the delays between frames as they were received.
+/// - `Accelerated`: With the original delays divided by the given factor.
+/// - `AsFastAsPossible`: Without delays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    Original,
+    Accelerated(f64),
+    AsFastAsPossible,
+}
+
+/// Reads back a file written by a [`FrameRecorder`].
+///
+/// # Example
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use tradier::wssession::{FrameReplay, ReplaySpeed};
+///
+/// #[tokio::main]
+/// async fn main() -> tradier::Result<()> {
+///     let replay = FrameReplay::open("session.rec").await?;
+///     let mut events = replay.events(ReplaySpeed::Accelerated(10.0));
+///     while let Some(event) = events.next().await {
+///         println!("{:?}", event?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct FrameReplay {
+    file: BufReader<File>,
+}
+
+impl FrameReplay {
+    /// Opens the recording at `path`.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path).await?;
+        Ok(FrameReplay {
+            file: BufReader::new(file),
+        })
+    }
+
+    /// Streams the recorded frames at `speed`.
+    ///
+    /// Records whose content cannot be parsed are yielded as [`Error::InvalidRecording`] without
+    /// ending the stream. A record that is truncated, as left by a crash while appending, or a
+    /// failure to read the file is yielded as an error and ends the stream.
+    pub fn frames(self, speed: ReplaySpeed) -> BoxStream<'static, Result<RecordedFrame>> {
+        let state = (self.file, 0u64, None::<(DateTime<Utc>, Instant)>, false);
+        stream::unfold(state, move |(mut file, index, start, done)| async move {
+            if done {
+                return None;
+            }
+            let (frame, done) = match read_record(&mut file, index).await {
+                Ok(None) => return None,
+                Ok(Some(frame)) => (frame, false),
+                Err(e) => (Err(e), true),
+            };
+            let start = match &frame {
+                Ok(frame) => {
+                    let start = start.unwrap_or((frame.received, Instant::now()));
+                    if let Some(delay) = replay_delay(speed, start.0, frame.received) {
+                        sleep_until(start.1 + delay).await;
+                    }
+                    Some(start)
+                }
+                Err(_) => start,
+            };
+            Some((frame, (file, index + 1, start, done)))
+        })
+        .boxed()
+    }
+
+    /// Streams the typed events of the recorded frames at `speed`, parsed as a live
+    /// [`MarketSession`](super::MarketSession) would parse them.
+    pub fn events(self, speed: ReplaySpeed) -> MarketEventStream {
+        MarketEventStream::from_frames(
+            self.frames(speed)
+                .map(|frame| frame.map(|frame| frame.frame)),
+        )
+    }
+}
+
+/// Returns how long after the first frame, received at `first`, a frame received at `received`
+/// is delivered, or `None` if it is delivered immediately.
+fn replay_delay(
+    speed: ReplaySpeed,
+    first: DateTime<Utc>,
+    received: DateTime<Utc>,
+) -> Option<Duration> {
+    let elapsed = (received - first).to_std().ok()?;
+    match speed {
+        ReplaySpeed::Original => Some(elapsed),
+        ReplaySpeed::Accelerated(factor) if factor > 0.0 => {
+            Duration::try_from_secs_f64(elapsed.as_secs_f64() / factor).ok()
+        }
+        ReplaySpeed::Accelerated(_) | ReplaySpeed::AsFastAsPossible => None,
+    }
+}
+
+/// Reads the record at `index`, or `None` at the end of the file. The outer error means the
+/// file cannot be read any further; the inner one that this record's content is invalid.
+async fn read_record(
+    file: &mut BufReader<File>,
+    index: u64,
+) -> Result<Option<Result<RecordedFrame>>> {
+    let invalid = |reason: &str| Error::InvalidRecording(index, reason.to_owned());
+    let mut prefix = Vec::new();
+    if file.read_until(b' ', &mut prefix).await? == 0 {
+        return Ok(None);
+    }
+    if prefix.pop() != Some(b' ') {
+        return Err(invalid("truncated length"));
+    }
+    let length: u64 = std::str::from_utf8(&prefix)
+        .ok()
+        .and_then(|prefix| prefix.trim_start().parse().ok())
+        .ok_or_else(|| invalid("invalid length"))?;
+
+    // The buffer grows with what is actually read, so a corrupt length cannot allocate more
+    // than the rest of the file.
+    let expected = length.saturating_add(1);
+    let mut json = Vec::new();
+    let read = (&mut *file).take(expected).read_to_end(&mut json).await?;
+    if (read as u64) < expected {
+        return Err(invalid("truncated record"));
+    }
+    if json.pop() != Some(b'\n') {
+        return Err(invalid("missing line break"));
+    }
+    Ok(Some(
+        serde_json::from_slice::<Record>(&json)
+            .map(|record| RecordedFrame {
+                received: record.received,
+                frame: record.frame.into_owned(),
+            })
+            .map_err(|e| Error::InvalidRecording(index, e.to_string())),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::wssession::MarketEvent;
+
+    const SUMMARY: &str = r#"{"type":"summary","symbol":"SPY","open":"282.42"}"#;
+    const QUOTE: &str = r#"{"type":"quote","symbol":"C","bid":1,"bidsz":1,"bidexch":"Q","biddate":"0","ask":2,"asksz":1,"askexch":"Q","askdate":"0"}"#;
+
+    fn recording(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("tradier-{}-{}.rec", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn at(millis: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(millis).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_recorded_frames_are_replayed() {
+        let path = recording("roundtrip");
+        let recorder = FrameRecorder::create(&path).await.unwrap();
+        recorder.record_at(at(1_000), SUMMARY);
+        recorder.clone().record_at(at(1_250), QUOTE);
+        recorder.flush().await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let first = contents.lines().next().unwrap();
+        let (length, json) = first.split_once(' ').unwrap();
+        assert_eq!(length.parse::<usize>().unwrap(), json.len());
+
+        let frames: Vec<_> = FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .frames(ReplaySpeed::AsFastAsPossible)
+            .collect()
+            .await;
+        let frames: Vec<RecordedFrame> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            frames,
+            vec![
+                RecordedFrame {
+                    received: at(1_000),
+                    frame: SUMMARY.to_owned()
+                },
+                RecordedFrame {
+                    received: at(1_250),
+                    frame: QUOTE.to_owned()
+                },
+            ]
+        );
+
+        let events: Vec<_> = FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .events(ReplaySpeed::AsFastAsPossible)
+            .collect()
+            .await;
+        assert!(matches!(events[0], Ok(MarketEvent::Summary(_))));
+        assert!(matches!(events[1], Ok(MarketEvent::Quote(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_and_truncated_records() {
+        let path = recording("invalid");
+        std::fs::write(
+            &path,
+            format!("7 {{\"t\":1}}\n2 {{}}\n{} {{\"t\":1", SUMMARY.len()),
+        )
+        .unwrap();
+
+        let frames: Vec<_> = FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .frames(ReplaySpeed::AsFastAsPossible)
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 3);
+        assert!(matches!(frames[0], Err(Error::InvalidRecording(0, _))));
+        assert!(matches!(frames[1], Err(Error::InvalidRecording(1, _))));
+        assert!(
+            matches!(&frames[2], Err(Error::InvalidRecording(2, reason)) if reason == "truncated record")
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_length_is_a_truncated_record() {
+        let path = recording("oversized");
+        std::fs::write(&path, format!("{} {{}}\n", u64::MAX)).unwrap();
+
+        let frames: Vec<_> = FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .frames(ReplaySpeed::AsFastAsPossible)
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 1);
+        assert!(
+            matches!(&frames[0], Err(Error::InvalidRecording(0, reason)) if reason == "truncated record")
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_frames_are_dropped_when_the_buffer_is_full() {
+        let path = recording("full");
+        let recorder = FrameRecorder::with_capacity(&path, 2).await.unwrap();
+        for millis in 0..5 {
+            recorder.clone().record_at(at(millis), SUMMARY);
+        }
+        assert_eq!(recorder.dropped_frames(), 3);
+        recorder.flush().await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_waits_for_the_recorded_delay() {
+        let path = recording("pace");
+        let recorder = FrameRecorder::create(&path).await.unwrap();
+        recorder.record_at(at(0), SUMMARY);
+        recorder.record_at(at(200), SUMMARY);
+        recorder.flush().await.unwrap();
+
+        let started = Instant::now();
+        let frames: Vec<_> = FrameReplay::open(&path)
+            .await
+            .unwrap()
+            .frames(ReplaySpeed::Accelerated(4.0))
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 2);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_delay() {
+        let delay = |speed| replay_delay(speed, at(1_000), at(3_000));
+        assert_eq!(delay(ReplaySpeed::Original), Some(Duration::from_secs(2)));
+        assert_eq!(
+            delay(ReplaySpeed::Accelerated(4.0)),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(delay(ReplaySpeed::AsFastAsPossible), None);
+        assert_eq!(
+            replay_delay(ReplaySpeed::Original, at(3_000), at(1_000)),
+            None
+        );
+    }
+}
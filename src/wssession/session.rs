@@ -7,19 +7,22 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use tracing::debug;
 
-use super::session_manager::SessionManager;
+use super::session_manager::{SessionGuard, SessionManager};
 
 /// Represents a Tradier API session, handling WebSocket streaming configuration for either
 /// account or market data.
+///
+/// A `Session` owns the [`SessionGuard`] of its type, so the slot in the `SessionManager` is
+/// released as soon as the session is dropped.
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub(crate) struct Session<'a> {
     /// The type of session, either `Account` or `Market`.
     pub session_type: SessionType,
     /// Contains information about the WebSocket stream, including URL and session ID.
     pub stream_info: StreamInfo,
-    created_at: DateTime<Utc>,
-    session_manager: &'a SessionManager,
+    last_used: DateTime<Utc>,
+    guard: SessionGuard<'a>,
 }
 
 /// Response structure for the Tradier API session request. Holds the stream information.
@@ -61,12 +64,12 @@ impl<'a> Session<'a> {
     /// managed by the provided `SessionManager`.
     ///
     /// This method handles the creation of a Tradier WebSocket session for either market or
-    /// account events. It ensures that only one session is active at any given time by leveraging
-    /// the `SessionManager` for singleton enforcement.
+    /// account events. It ensures that only one session of each type is active at any given time
+    /// by leveraging the `SessionManager` for singleton enforcement.
     ///
     /// # Parameters
     /// - **`session_manager`**: A reference to the `SessionManager` that manages session state and ensures
-    ///   only one active session exists at a time.
+    ///   only one active session of each type exists at a time.
     /// - **`session_type`**: Specifies the type of session to create:
     ///   - `SessionType::Market`: For streaming market data.
     ///   - `SessionType::Account`: For streaming account-related events.
@@ -78,14 +81,15 @@ impl<'a> Session<'a> {
     /// - **`Err(Error)`**: An error if session creation fails.
     ///
     /// # Behavior
-    /// - Acquires a lock from the `SessionManager` to ensure a singleton session.
+    /// - Acquires the slot of `session_type` from the `SessionManager` to ensure a singleton session.
     /// - Constructs the appropriate URL based on the session type.
     /// - Sends a POST request to the Tradier API to initialize the session.
     /// - Parses the response to extract the session stream information.
     ///
     /// # Errors
     /// This method will return an error if:
-    /// - **Singleton Restriction**: Another session is already active (`SessionManager` prevents new sessions).
+    /// - **Singleton Restriction**: Another session of the same type is already active
+    ///   (`SessionManager` prevents new sessions).
     /// - **Missing Access Token**: The `access_token` field in the provided `Config` is `None`.
     /// - **Network or API Issues**: The HTTP request to create the session fails due to:
     ///   - Network errors.
@@ -99,7 +103,7 @@ impl<'a> Session<'a> {
     /// #[tokio::main]
     /// async fn main() {
     ///     let config = Config::new();
-    ///     let session_manager = SessionManager::new();
+    ///     let session_manager = SessionManager::default();
     ///
     ///     match Session::new_with_session_manager(&session_manager, SessionType::Account, &config).await {
     ///         Ok(session) => {
@@ -120,73 +124,99 @@ impl<'a> Session<'a> {
     /// This can help diagnose issues with API connectivity or authentication.
     ///
     /// # Note
-    /// The session must be explicitly released by the `SessionManager` when no longer needed to allow
-    /// creation of new sessions.
+    /// The slot is released when the session is dropped, or right away if creation fails.
     pub(crate) async fn new_with_session_manager(
         session_manager: &'a SessionManager,
         session_type: SessionType,
         config: &Config,
     ) -> Result<Self> {
-        match session_manager.acquire_session() {
-            Ok(_) => {
-                let client = HttpClient::new();
-                let url = match session_type {
-                    SessionType::Market => {
-                        format!("{}/v1/markets/events/session", config.rest_api.base_url)
-                    }
-                    SessionType::Account => {
-                        format!("{}/v1/accounts/events/session", config.rest_api.base_url)
-                    }
-                };
-                debug!("Url to use to get the Session ID: {}", url);
-
-                let access_token = config
-                    .credentials
-                    .access_token
-                    .as_ref()
-                    .ok_or(Error::MissingAccessToken)?;
-
-                let response = client
-                    .post(&url)
-                    .header("Authorization", format!("Bearer {}", access_token))
-                    .header("Accept", "application/json")
-                    .header("Content-Length", "0")
-                    .body("")
-                    .send()
-                    .await?;
-
-                let status = response.status();
-                let headers = response.headers().clone();
-                debug!("Response status: {}", status);
-                debug!("Response headers: {:?}", headers);
-
-                let body = response.text().await?;
-                debug!("Response body: {}", body);
-
-                if status.is_success() {
-                    let session_response: SessionResponse = serde_json::from_str(&body)?;
-                    Ok(Session {
-                        session_type,
-                        stream_info: session_response.stream,
-                        created_at: Utc::now(),
-                        session_manager,
-                    })
-                } else {
-                    session_manager.release_session();
-                    Err(Error::CreateSessionError(session_type, status, body))
-                }
+        let guard = session_manager.acquire_session(session_type.clone())?;
+        let stream_info = Self::request_stream_info(&session_type, config).await?;
+        Ok(Session {
+            session_type,
+            stream_info,
+            last_used: Utc::now(),
+            guard,
+        })
+    }
+
+    /// Asks Tradier for a new session id and stream URL for `session_type`.
+    async fn request_stream_info(
+        session_type: &SessionType,
+        config: &Config,
+    ) -> Result<StreamInfo> {
+        let client = HttpClient::new();
+        let url = match session_type {
+            SessionType::Market => {
+                format!("{}/v1/markets/events/session", config.rest_api.base_url)
             }
-            Err(e) => Err(e),
+            SessionType::Account => {
+                format!("{}/v1/accounts/events/session", config.rest_api.base_url)
+            }
+        };
+        debug!("Url to use to get the Session ID: {}", url);
+
+        let access_token = config
+            .credentials
+            .access_token
+            .as_ref()
+            .ok_or(Error::MissingAccessToken)?;
+
+        let response = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Accept", "application/json")
+            .header("Content-Length", "0")
+            .body("")
+            .send()
+            .await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        debug!("Response status: {}", status);
+        debug!("Response headers: {:?}", headers);
+
+        let body = response.text().await?;
+        debug!("Response body: {}", body);
+
+        if status.is_success() {
+            let session_response: SessionResponse = serde_json::from_str(&body)?;
+            Ok(session_response.stream)
+        } else {
+            Err(Error::CreateSessionError(
+                session_type.clone(),
+                status,
+                body,
+            ))
         }
     }
 
     /// Checks if the session has expired based on the configured session timeout.
     ///
+    /// Tradier expires session ids that have not been used for `TRADIER_SESSION_TIMEOUT`
+    /// minutes, so the timeout counts from the later of the creation of the session and the
+    /// last call to [`mark_used`](Session::mark_used).
+    ///
     /// # Returns
-    /// - `true` if the session duration exceeds `TRADIER_SESSION_TIMEOUT`, otherwise `false`.
-    #[allow(dead_code)]
+    /// - `true` if the session has been unused for longer than `TRADIER_SESSION_TIMEOUT`,
+    ///   otherwise `false`.
     pub fn is_expired(&self) -> bool {
-        Utc::now() - self.created_at > Duration::minutes(TRADIER_SESSION_TIMEOUT)
+        Utc::now() - self.last_used > Duration::minutes(TRADIER_SESSION_TIMEOUT)
+    }
+
+    /// Records that the session id was in use until now, e.g. by a connection that just dropped.
+    pub(crate) fn mark_used(&mut self) {
+        self.last_used = Utc::now();
+    }
+
+    /// Replaces the session id and stream URL with freshly created ones of the same type.
+    ///
+    /// The session keeps its slot in the `SessionManager`. If creating the new session fails,
+    /// the old session information is kept.
+    pub(crate) async fn renew(&mut self, config: &Config) -> Result<()> {
+        self.stream_info = Self::request_stream_info(&self.session_type, config).await?;
+        self.last_used = Utc::now();
+        Ok(())
     }
 
     /// Retrieves the WebSocket URL associated with the session.
@@ -392,6 +422,7 @@ mod tests_session {
                 ws_base_url: "".to_string(),
                 events_path: "".to_string(),
                 reconnect_interval: 5,
+                transport: Default::default(),
             },
         };
 
@@ -447,4 +478,111 @@ mod tests_session {
 
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn test_renew_replaces_the_session() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/accounts/events/session")
+            .with_status(200)
+            .with_body(
+                r#"{"stream":{"url":"wss://ws.tradier.com/v1/accounts/events","sessionid":"renewed"}}"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let mut session =
+            Session::new_with_session_manager(&session_manager, SessionType::Account, &config)
+                .await
+                .unwrap();
+        session.last_used = Utc::now() - Duration::minutes(TRADIER_SESSION_TIMEOUT + 1);
+        assert!(session.is_expired());
+
+        session.renew(&config).await.unwrap();
+        assert!(!session.is_expired());
+        assert_eq!(session.session_type, SessionType::Account);
+        assert_eq!(session.get_session_id(), "renewed");
+        assert!(session_manager
+            .acquire_session(SessionType::Account)
+            .is_err());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_mark_used_postpones_expiry() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(r#"{"stream":{"url":"wss://ws.tradier.com","sessionid":"id"}}"#)
+            .create_async()
+            .await;
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let mut session =
+            Session::new_with_session_manager(&session_manager, SessionType::Market, &config)
+                .await
+                .unwrap();
+        session.last_used = Utc::now() - Duration::minutes(TRADIER_SESSION_TIMEOUT + 1);
+        session.mark_used();
+        assert!(!session.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_dropping_a_session_releases_its_slot() {
+        let mut server = Server::new_async().await;
+        let body = r#"{"stream":{"url":"wss://ws.tradier.com","sessionid":"id"}}"#;
+        let market_mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(200)
+            .with_body(body)
+            .expect(2)
+            .create_async()
+            .await;
+        let account_mock = server
+            .mock("POST", "/v1/accounts/events/session")
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let market =
+            Session::new_with_session_manager(&session_manager, SessionType::Market, &config)
+                .await
+                .unwrap();
+        let _account =
+            Session::new_with_session_manager(&session_manager, SessionType::Account, &config)
+                .await
+                .unwrap();
+
+        drop(market);
+        let market =
+            Session::new_with_session_manager(&session_manager, SessionType::Market, &config).await;
+        assert!(market.is_ok());
+        market_mock.assert_async().await;
+        account_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_creation_releases_its_slot() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/v1/markets/events/session")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let config = create_test_config().server_url(&server.url()).finish();
+        let session_manager = SessionManager::default();
+        let session =
+            Session::new_with_session_manager(&session_manager, SessionType::Market, &config).await;
+        assert!(matches!(session, Err(Error::CreateSessionError(..))));
+        assert!(session_manager.acquire_session(SessionType::Market).is_ok());
+    }
 }
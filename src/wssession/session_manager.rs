@@ -4,15 +4,19 @@ use std::sync::LazyLock;
 use crate::Error;
 use crate::Result;
 
-/// Manages the existence of a single session within a system.
+use super::session::SessionType;
+
+/// Manages the existence of a single session of each type within a system.
 ///
-/// `SessionManager` is an internal utility that enforces a single active session
-/// at any given time. It is primarily used internally by higher-level abstractions like [`Session`]
-/// to coordinate access to WebSocket sessions or other shared resources.
+/// `SessionManager` is an internal utility that enforces at most one active market session and
+/// one active account session at any given time, matching what Tradier permits concurrently. It
+/// is primarily used internally by higher-level abstractions like [`Session`] to coordinate
+/// access to WebSocket sessions.
 ///
 /// ### Key Features
 /// - **Thread-Safe**: Uses atomic operations to safely manage session state in multi-threaded environments.
-/// - **Lifecycle Management**: Ensures proper acquisition and release of session state.
+/// - **Lifecycle Management**: Acquiring a slot returns a [`SessionGuard`] that releases it when
+///   dropped, so a slot is held exactly as long as the session that owns the guard.
 /// - **Internal Use Only**: This type is not exposed outside of the crate. Use [`Session`] or
 ///   other public abstractions for interacting with session-related functionality.
 ///
@@ -20,10 +24,6 @@ use crate::Result;
 /// - Only one `SessionManager` instance should exist in the system. Use the
 ///   [`GLOBAL_SESSION_MANAGER`] for global access in multi-threaded contexts.
 ///
-/// ### Internal Use
-/// Developers working within this crate can use `SessionManager` to manage session lifecycle
-/// explicitly. For most external users, session management is handled transparently by [`Session`].
-///
 /// # Examples
 ///
 /// ## Internal Use: Acquiring and Releasing a Session
@@ -34,54 +34,64 @@ use crate::Result;
 /// use tradier::wssession::session_manager::SessionManager;
 ///
 /// let manager = SessionManager::default();
-/// assert!(manager.acquire_session().is_ok());
-/// assert!(manager.acquire_session().is_err()); // Second acquisition fails
-/// manager.release_session(); // Releases the session
-/// assert!(manager.acquire_session().is_ok()); // Session can be reacquired
+/// let guard = manager.acquire_session(SessionType::Market).unwrap();
+/// assert!(manager.acquire_session(SessionType::Market).is_err()); // Second acquisition fails
+/// assert!(manager.acquire_session(SessionType::Account).is_ok()); // Other slot is independent
+/// drop(guard); // Releases the market slot
+/// assert!(manager.acquire_session(SessionType::Market).is_ok()); // Session can be reacquired
 /// ```
+///
+/// [`Session`]: super::session::Session
 #[derive(Default, Debug)]
 pub(crate) struct SessionManager {
-    session_exists: AtomicBool,
+    market_session_exists: AtomicBool,
+    account_session_exists: AtomicBool,
 }
 
 impl SessionManager {
-    /// Attempts to acquire a session, ensuring only one active session at a time.
+    /// Attempts to acquire the slot of the given session type.
     ///
-    /// This method atomically sets the session state to "active". If a session is already
-    /// active, it returns an error indicating that the session cannot be acquired.
-    ///
-    /// This method is for internal use by higher-level abstractions like [`Session`].
+    /// This method atomically sets the slot to "active". If a session of the same type is
+    /// already active, it returns an error indicating that the session cannot be acquired.
     ///
     /// # Returns
-    /// - `Ok(())` if the session was successfully acquired.
-    /// - `Err(Error::SessionAlreadyExists)` if a session is already active.
-    pub(crate) fn acquire_session(&self) -> Result<()> {
+    /// - `Ok(SessionGuard)` if the slot was acquired. The slot is released when the guard is
+    ///   dropped.
+    /// - `Err(Error::SessionAlreadyExists)` if a session of that type is already active.
+    pub(crate) fn acquire_session(&self, session_type: SessionType) -> Result<SessionGuard<'_>> {
         if self
-            .session_exists
+            .slot(&session_type)
             .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
             .is_err()
         {
             return Err(Error::SessionAlreadyExists);
         }
-        Ok(())
+        Ok(SessionGuard {
+            manager: self,
+            session_type,
+        })
     }
 
-    /// Releases the active session, allowing a new session to be acquired.
-    ///
-    /// This method atomically sets the session state to "inactive". It is for internal
-    /// use by components like [`Session`] to manage session lifecycles.
-    pub(crate) fn release_session(&self) {
-        self.session_exists.store(false, Ordering::Release);
+    fn slot(&self, session_type: &SessionType) -> &AtomicBool {
+        match session_type {
+            SessionType::Market => &self.market_session_exists,
+            SessionType::Account => &self.account_session_exists,
+        }
     }
 }
 
-impl Drop for SessionManager {
-    /// Automatically releases the session when the `SessionManager` is dropped.
-    ///
-    /// This ensures that any active session is released when a `SessionManager` instance
-    /// goes out of scope. This method is primarily for internal use.
+/// Holds the slot of one session type in a [`SessionManager`] and releases it on drop.
+#[derive(Debug)]
+pub(crate) struct SessionGuard<'a> {
+    manager: &'a SessionManager,
+    session_type: SessionType,
+}
+
+impl Drop for SessionGuard<'_> {
     fn drop(&mut self) {
-        self.release_session();
+        self.manager
+            .slot(&self.session_type)
+            .store(false, Ordering::Release);
     }
 }
 
@@ -103,3 +113,33 @@ impl Drop for SessionManager {
 /// consumers of the library should use public abstractions like [`Session`].
 pub(crate) static GLOBAL_SESSION_MANAGER: LazyLock<SessionManager> =
     LazyLock::new(SessionManager::default);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_releases_its_slot_on_drop() {
+        let manager = SessionManager::default();
+        let guard = manager.acquire_session(SessionType::Market).unwrap();
+        assert!(matches!(
+            manager.acquire_session(SessionType::Market),
+            Err(Error::SessionAlreadyExists)
+        ));
+
+        drop(guard);
+        assert!(manager.acquire_session(SessionType::Market).is_ok());
+    }
+
+    #[test]
+    fn test_market_and_account_slots_are_independent() {
+        let manager = SessionManager::default();
+        let _market = manager.acquire_session(SessionType::Market).unwrap();
+        let account = manager.acquire_session(SessionType::Account).unwrap();
+        assert!(manager.acquire_session(SessionType::Account).is_err());
+
+        drop(account);
+        assert!(manager.acquire_session(SessionType::Account).is_ok());
+        assert!(manager.acquire_session(SessionType::Market).is_err());
+    }
+}
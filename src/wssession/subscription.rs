@@ -0,0 +1,146 @@
+//! # Live Subscription Updates
+//!
+//! A [`SubscriptionHandle`] changes the symbols and filters of a running market stream. Every
+//! change is sent to Tradier as an updated [`MarketSessionPayload`] on the open connection, and
+//! the handle keeps the latest payload so that reconnections subscribe to the current set.
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+use super::{MarketSessionFilter, MarketSessionPayload};
+
+/// Changes the subscription of a market stream created by
+/// [`MarketSession::subscribe`](super::MarketSession::subscribe).
+///
+/// Handles are cheap to clone and can be used from any task. Changes that leave the
+/// subscription as it was are not sent. Once the stream is dropped, changes are still recorded
+/// but no longer sent anywhere.
+#[derive(Debug, Clone)]
+pub struct SubscriptionHandle {
+    payload: Arc<watch::Sender<MarketSessionPayload<'static>>>,
+}
+
+impl SubscriptionHandle {
+    pub(crate) fn new(
+        payload: MarketSessionPayload<'static>,
+    ) -> (Self, watch::Receiver<MarketSessionPayload<'static>>) {
+        let (sender, receiver) = watch::channel(payload);
+        let handle = SubscriptionHandle {
+            payload: Arc::new(sender),
+        };
+        (handle, receiver)
+    }
+
+    /// Adds symbols to the subscription. Symbols that are already subscribed are ignored.
+    pub fn add_symbols<I, S>(&self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let added: Vec<String> = symbols.into_iter().map(Into::into).collect();
+        self.payload.send_if_modified(|payload| {
+            let current = payload.symbols.to_mut();
+            let before = current.len();
+            for symbol in added {
+                if !current.contains(&symbol) {
+                    current.push(symbol);
+                }
+            }
+            current.len() != before
+        });
+    }
+
+    /// Removes symbols from the subscription. Symbols that are not subscribed are ignored.
+    pub fn remove_symbols<I, S>(&self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let removed: Vec<S> = symbols.into_iter().collect();
+        self.payload.send_if_modified(|payload| {
+            let current = payload.symbols.to_mut();
+            let before = current.len();
+            current.retain(|symbol| !removed.iter().any(|r| r.as_ref() == symbol));
+            current.len() != before
+        });
+    }
+
+    /// Replaces the subscribed symbols.
+    pub fn set_symbols<I, S>(&self, symbols: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
+        self.payload.send_if_modified(|payload| {
+            if *payload.symbols == *symbols {
+                return false;
+            }
+            payload.symbols = Cow::Owned(symbols);
+            true
+        });
+    }
+
+    /// Replaces the filters of the subscription. `None` subscribes to every event type.
+    pub fn set_filters(&self, filters: Option<&[MarketSessionFilter]>) {
+        self.payload.send_if_modified(|payload| {
+            if payload.filters.as_deref() == filters {
+                return false;
+            }
+            payload.filters = filters.map(|filters| Cow::Owned(filters.to_vec()));
+            true
+        });
+    }
+
+    /// Returns the currently subscribed symbols.
+    pub fn symbols(&self) -> Vec<String> {
+        self.payload.borrow().symbols.to_vec()
+    }
+
+    /// Returns the current filters, `None` meaning every event type.
+    pub fn filters(&self) -> Option<Vec<MarketSessionFilter>> {
+        self.payload.borrow().filters.as_deref().map(<[_]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle() -> (
+        SubscriptionHandle,
+        watch::Receiver<MarketSessionPayload<'static>>,
+    ) {
+        let symbols = ["SPY".to_owned()];
+        SubscriptionHandle::new(MarketSessionPayload::recommended(&symbols, "session").into_owned())
+    }
+
+    #[test]
+    fn test_add_and_remove_symbols() {
+        let (handle, mut receiver) = handle();
+
+        handle.add_symbols(["AAPL", "SPY", "C"]);
+        assert_eq!(handle.symbols(), vec!["SPY", "AAPL", "C"]);
+        assert!(receiver.has_changed().unwrap());
+        receiver.mark_unchanged();
+
+        handle.remove_symbols(["SPY", "MSFT"]);
+        assert_eq!(receiver.borrow_and_update().symbols, vec!["AAPL", "C"]);
+    }
+
+    #[test]
+    fn test_unchanged_subscription_is_not_sent() {
+        let (handle, receiver) = handle();
+
+        handle.add_symbols(["SPY"]);
+        handle.remove_symbols(["MSFT"]);
+        handle.set_symbols(["SPY"]);
+        handle.set_filters(Some(&[MarketSessionFilter::QUOTE]));
+        assert!(!receiver.has_changed().unwrap());
+
+        handle.set_filters(None);
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(handle.filters(), None);
+    }
+}